@@ -0,0 +1,175 @@
+/// A line on a chart, values that are `None` leave a gap in the line
+pub struct Series<'a> {
+    pub name: &'a str,
+    pub color: &'a str,
+    /// Draw the line dashed instead of solid
+    pub dashed: bool,
+    pub values: Vec<Option<f32>>,
+}
+
+const CHART_WIDTH: f32 = 600.0;
+const LINE_CHART_HEIGHT: f32 = 200.0;
+const BAR_CHART_HEIGHT: f32 = 120.0;
+/// The space on the left for the value labels
+const MARGIN_LEFT: f32 = 45.0;
+/// The space at the top for the legend
+const MARGIN_TOP: f32 = 20.0;
+/// The space at the bottom for the labels of each point
+const MARGIN_BOTTOM: f32 = 20.0;
+const MARGIN_RIGHT: f32 = 10.0;
+const FONT: &str = r#"font-family="sans-serif" font-size="11""#;
+
+/// Draw each series as a line over the `labels` along the bottom, as an svg
+pub fn line_chart(labels: &[String], series: &[Series]) -> String {
+    let values = series.iter().flat_map(|s| s.values.iter().flatten());
+    let (min, max) = values.fold((f32::MAX, f32::MIN), |(min, max), value| {
+        (min.min(*value), max.max(*value))
+    });
+    if min > max {
+        return empty_chart(LINE_CHART_HEIGHT);
+    }
+    // leave some room above and below the lines, and still a range when every value is the same
+    let padding = ((max - min) * 0.1).max(5.0);
+    let (min, max) = ((min - padding).max(0.0), max + padding);
+    let plot_height = LINE_CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let y = |value: f32| MARGIN_TOP + plot_height * (1.0 - (value - min) / (max - min));
+    let mut s = svg_start(LINE_CHART_HEIGHT);
+    for idx in 0..=4 {
+        let value = min + (max - min) * idx as f32 / 4.0;
+        s.push_str(&format!(
+            r##"<line x1="{x1}" y1="{y}" x2="{x2}" y2="{y}" stroke="#ddd"/><text x="{tx}" y="{ty}" text-anchor="end" {font}>{value}</text>"##,
+            x1 = MARGIN_LEFT,
+            x2 = CHART_WIDTH - MARGIN_RIGHT,
+            y = y(value),
+            tx = MARGIN_LEFT - 4.0,
+            ty = y(value) + 4.0,
+            font = FONT,
+            value = value.round(),
+        ));
+    }
+    s.push_str(&x_labels(labels, LINE_CHART_HEIGHT));
+    let mut legend_x = MARGIN_LEFT;
+    for series in series {
+        let dash = if series.dashed {
+            r#" stroke-dasharray="6 4""#
+        } else {
+            ""
+        };
+        // a gap in the values starts a new line
+        let mut points = Vec::new();
+        let mut lines = Vec::new();
+        for (idx, value) in series.values.iter().enumerate() {
+            match value {
+                Some(value) => points.push((x(idx, labels.len()), y(*value))),
+                None if !points.is_empty() => lines.push(std::mem::take(&mut points)),
+                None => {}
+            }
+        }
+        lines.push(points);
+        for line in lines.iter().filter(|line| !line.is_empty()) {
+            let points: Vec<String> = line.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+            s.push_str(&format!(
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"{}/>"#,
+                points.join(" "),
+                series.color,
+                dash
+            ));
+            for (x, y) in line {
+                s.push_str(&format!(
+                    r#"<circle cx="{}" cy="{}" r="3" fill="{}"/>"#,
+                    x, y, series.color
+                ));
+            }
+        }
+        s.push_str(&format!(
+            r#"<line x1="{x1}" y1="8" x2="{x2}" y2="8" stroke="{color}" stroke-width="2"{dash}/><text x="{tx}" y="12" {font}>{name}</text>"#,
+            x1 = legend_x,
+            x2 = legend_x + 20.0,
+            tx = legend_x + 24.0,
+            color = series.color,
+            dash = dash,
+            font = FONT,
+            name = series.name,
+        ));
+        legend_x += 34.0 + series.name.chars().count() as f32 * 7.0;
+    }
+    s.push_str("</svg>");
+    s
+}
+
+/// Draw a bar for each value over the `labels` along the bottom, as an svg
+pub fn bar_chart(labels: &[String], name: &str, color: &str, values: &[u32]) -> String {
+    let max = match values.iter().max() {
+        Some(max) if *max > 0 => *max as f32,
+        _ => return empty_chart(BAR_CHART_HEIGHT),
+    };
+    let plot_height = BAR_CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let slot = (CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / values.len() as f32;
+    let width = (slot * 0.6).min(40.0);
+    let mut s = svg_start(BAR_CHART_HEIGHT);
+    s.push_str(&format!(
+        r#"<text x="{}" y="12" {}>{}</text>"#,
+        MARGIN_LEFT, FONT, name
+    ));
+    for (idx, value) in values.iter().enumerate() {
+        let height = plot_height * *value as f32 / max;
+        let top = MARGIN_TOP + plot_height - height;
+        let x = x(idx, values.len());
+        s.push_str(&format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/><text x="{}" y="{}" text-anchor="middle" {}>{}</text>"#,
+            x - width / 2.0,
+            top,
+            width,
+            height,
+            color,
+            x,
+            top - 3.0,
+            FONT,
+            value
+        ));
+    }
+    s.push_str(&x_labels(labels, BAR_CHART_HEIGHT));
+    s.push_str("</svg>");
+    s
+}
+
+/// The middle of the space for point `idx` of `count`
+fn x(idx: usize, count: usize) -> f32 {
+    let slot = (CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / count.max(1) as f32;
+    MARGIN_LEFT + slot * (idx as f32 + 0.5)
+}
+
+fn x_labels(labels: &[String], height: f32) -> String {
+    labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| {
+            format!(
+                r#"<text x="{}" y="{}" text-anchor="middle" {}>{}</text>"#,
+                x(idx, labels.len()),
+                height - 5.0,
+                FONT,
+                label
+            )
+        })
+        .collect()
+}
+
+fn empty_chart(height: f32) -> String {
+    let mut s = svg_start(height);
+    s.push_str(&format!(
+        r##"<text x="{}" y="{}" text-anchor="middle" fill="#888" {}>Nothing logged yet</text></svg>"##,
+        CHART_WIDTH / 2.0,
+        height / 2.0,
+        FONT
+    ));
+    s
+}
+
+fn svg_start(height: f32) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = CHART_WIDTH,
+        h = height,
+    )
+}
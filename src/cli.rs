@@ -0,0 +1,597 @@
+//! The command line arguments for each of the binary's subcommands
+
+use std::{path::PathBuf, str::FromStr};
+
+use chrono::NaiveDate;
+
+use five_three_one::{
+    diagram::PlateColors,
+    file::Format,
+    import::{Source, Unit},
+    layout::PageSize,
+    plan::{Lift, WarmUp, DEFAULT_WARM_UP},
+    stats::Period,
+    strength::Sex,
+    weight::Weight,
+};
+use structopt::StructOpt;
+
+// the arguments are only parsed once, so there's no need to box the larger ones
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, StructOpt)]
+pub enum Args {
+    /// Generate a 5/3/1 plan
+    Generate(GenerateArgs),
+    /// Estimate a one rep max from a weight and reps
+    OneRep(OneRepArgs),
+    /// Calculate all of the weights that can be provided
+    /// by a set of plates, this is helpful since unique combinations
+    /// of weights can be expensive to calculate
+    WeightCombos(WeightComboArgs),
+    /// Convert an inventory file into a weights file or a weights file into an inventory file
+    Convert(ConvertArgs),
+    /// Step through today's session from your profile and add the results to your training log
+    Session(SessionArgs),
+    /// Show how your estimated 1 rep maxes, training maxes and volume have changed over your training log
+    Stats(StatsArgs),
+    /// Add the main lifts from a Strong, Hevy, FitNotes or spreadsheet csv export to your training log
+    Import(ImportArgs),
+    /// Suggest training maxes from recent top sets, entered here or from your training log
+    Suggest(SuggestArgs),
+    /// Save a printable html report charting your training log, with your records and any stalls
+    Report(ReportArgs),
+    /// Record your bodyweight in your training log, or list the bodyweights recorded
+    Bodyweight(BodyweightArgs),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct GenerateArgs {
+    /// Your known maximum 1 rep max squat
+    #[structopt(short, long)]
+    pub squat_max: f32,
+
+    /// Your known maximum 1 rep max dead lift
+    #[structopt(short, long)]
+    pub dead_max: f32,
+
+    /// Your known maximum 1 rep max bench press
+    #[structopt(short, long)]
+    pub bench_max: f32,
+
+    /// Your known maximum 1 rep max overhead press
+    #[structopt(short, long)]
+    pub ohp_max: f32,
+
+    /// How many months you'd like to generate
+    #[structopt(short, long)]
+    pub months: u32,
+
+    /// A path to a .toml, .json or .yaml file including all of your plate sets
+    /// This can be generated using the weight-combos command
+    #[structopt(short, long, conflicts_with = "inventory")]
+    pub weights_path: Option<PathBuf>,
+
+    /// A path to a .toml, .json or .yaml file listing the bars, plates and collars you own
+    #[structopt(short, long)]
+    pub inventory: Option<PathBuf>,
+
+    /// The name of the bar to use from the inventory, defaults to the first bar listed
+    #[structopt(long, requires = "inventory")]
+    pub bar: Option<String>,
+
+    /// The weight of each collar, this replaces the collar in the inventory if there is one
+    #[structopt(long)]
+    pub collar: Option<Weight>,
+
+    /// Round the weights for a lift to a multiple of something other than 5, formatted as
+    /// <lift>=<weight> for example ohp=1, this can be repeated for each lift
+    #[structopt(long)]
+    pub rounding: Vec<LiftValue<Weight>>,
+
+    /// Change how much a lift's training max goes up each cycle from +10 for squats and deads
+    /// and +5 for bench and ohp, formatted as <lift>=<weight> for example ohp=2.5
+    #[structopt(long)]
+    pub increment: Vec<LiftValue<Weight>>,
+
+    /// Allow one side of the bar to be heavier than the other by up to this much when a
+    /// weight can't be reached with both sides loaded the same
+    #[structopt(long, default_value = "0")]
+    pub max_imbalance: Weight,
+
+    /// A path to a .toml, .json or .yaml file including all of the extra
+    /// exercises you have planned for each workout, if not provided 4x45 1x35 1x25 2x10 1x5 1x2.5 is assumed
+    #[structopt(short, long)]
+    pub extra_path: Option<PathBuf>,
+
+    /// A path to a .toml, .json or .yaml file with your personal records, each amrap set
+    /// will show the reps needed to beat your best estimated 1 rep max
+    #[structopt(short, long)]
+    pub records: Option<PathBuf>,
+
+    /// Your bodyweight, for supporting exercises loaded relative to it
+    #[structopt(long)]
+    pub bodyweight: Option<Weight>,
+
+    /// What the maxes provided are, options include one-rep-max,training-max use
+    /// training-max when generating after you've started
+    #[structopt(long, default_value = "one-rep-max")]
+    pub input: MaxInput,
+
+    /// The percent of each 1 rep max used as the training max, formatted as a percent for
+    /// every lift or <lift>=<percent> for example ohp=85, this can be repeated for each lift
+    #[structopt(long)]
+    pub tm_percent: Vec<PercentArg>,
+
+    /// The path of the html file you'd like the plan saved to
+    #[structopt(short, long)]
+    pub file: Option<PathBuf>,
+
+    /// Browse the plan one week at a time, adjusting the training maxes and rounding
+    /// as you go, the plan can be exported from there
+    #[structopt(short = "I", long, conflicts_with = "file")]
+    pub interactive: bool,
+
+    /// The paper size the html plan will be printed on, options include letter,legal,a4,a5
+    /// or a custom size formatted as <width>x<height>[in|mm] for example 210x297mm
+    #[structopt(short, long, default_value = "letter")]
+    pub page_size: PageSize,
+
+    /// How many days should be printed on each page of the html plan
+    #[structopt(long, default_value = "2")]
+    pub days_per_page: u8,
+
+    /// Draw the plates for each set as a barbell in the html plan, along with
+    /// the plates to add or remove from the previous set
+    #[structopt(long)]
+    pub plate_diagrams: bool,
+
+    /// Include the plates to add and remove before each set
+    #[structopt(long)]
+    pub plate_changes: bool,
+
+    /// The warm up sets done before the work sets each day, formatted as a comma separated list
+    /// of <percent>x<reps> where percent is of your training max, `bar` can be included for an
+    /// empty bar set for example bar,40x5,50x5,60x3
+    #[structopt(long, default_value = DEFAULT_WARM_UP)]
+    pub warm_up: WarmUp,
+
+    /// How many weeks to print side by side, defaults to as many as fit in the terminal
+    /// or 4 when the plan isn't printed to a terminal
+    #[structopt(long)]
+    pub columns: Option<usize>,
+
+    /// When to highlight the amrap sets with colours, options include auto,always,never
+    #[structopt(long, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Don't include any warm up sets
+    #[structopt(long)]
+    pub no_warm_up: bool,
+
+    /// The colours used for plates in the diagrams, options include plain,iwf (kg plates)
+    #[structopt(long, default_value = "plain")]
+    pub plate_colors: PlateColors,
+
+    /// If the weights, inventory or extra files can't be read, warn and use the defaults instead of failing
+    #[structopt(long)]
+    pub lenient: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct OneRepArgs {
+    #[structopt(short, long)]
+    pub weight: f32,
+    #[structopt(short, long)]
+    pub reps: u8,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct WeightComboArgs {
+    /// Weights you own, each -w flag should be formatted as
+    /// <wt>[x<ct>] for example 45 would be 1 45 lb weight while
+    /// 25x6 would be 6 25 lb weights
+    #[structopt(short, long, conflicts_with = "inventory")]
+    pub weights: Vec<WeightArg>,
+    /// A path to an inventory file to use instead of -w
+    #[structopt(short, long)]
+    pub inventory: Option<PathBuf>,
+    /// The name of the bar to use from the inventory, defaults to the first bar listed
+    #[structopt(long, requires = "inventory")]
+    pub bar: Option<String>,
+    /// Optionally if you'd like to have the values printed to a file
+    /// defaults to stdout
+    #[structopt(short, long)]
+    pub output: Option<PathBuf>,
+    /// Format for printing, options include toml,json,yaml
+    #[structopt(short, long)]
+    pub format: Option<WeightsFormat>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ConvertArgs {
+    /// The inventory or weights file to convert
+    pub input: PathBuf,
+    /// What to convert the input into, options include inventory,weights
+    #[structopt(short, long)]
+    pub to: ConvertTo,
+    /// The name of the bar to use when converting an inventory, defaults to the first bar listed
+    #[structopt(long)]
+    pub bar: Option<String>,
+    /// Optionally if you'd like to have the result printed to a file
+    /// defaults to stdout
+    #[structopt(short, long)]
+    pub output: Option<PathBuf>,
+    /// Format for printing, options include toml,json,yaml defaults to
+    /// the extension of the output file or toml
+    #[structopt(short, long)]
+    pub format: Option<Format>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SessionArgs {
+    /// A path to a .toml, .json or .yaml file with your training maxes and schedule
+    #[structopt(short, long)]
+    pub profile: PathBuf,
+    /// The day to do the session for, formatted as YYYY-MM-DD, defaults to today
+    #[structopt(long)]
+    pub date: Option<NaiveDate>,
+    /// Count down this many seconds of rest after each work and supplemental set
+    #[structopt(long)]
+    pub rest: Option<u64>,
+    /// The training log to add the results to, defaults to the log in your profile
+    #[structopt(short, long)]
+    pub log: Option<PathBuf>,
+    /// The personal records file to update, defaults to the records in your profile
+    #[structopt(short, long)]
+    pub records: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct StatsArgs {
+    /// A path to your profile, the training log in it is used
+    #[structopt(short, long)]
+    pub profile: Option<PathBuf>,
+    /// A path to the training log, instead of the one in your profile
+    #[structopt(short, long, required_unless = "profile")]
+    pub log: Option<PathBuf>,
+    /// How much of the log each row covers, options include week,cycle
+    #[structopt(long, default_value = "week")]
+    pub by: Period,
+    /// Flag a lift as stalled when its estimated 1 rep max hasn't gone up in this many cycles
+    #[structopt(long, default_value = "2")]
+    pub stall_cycles: u32,
+    /// Optionally if you'd like to have the stats printed to a file
+    /// defaults to stdout
+    #[structopt(short, long)]
+    pub output: Option<PathBuf>,
+    /// Format for printing, options include table,csv,json defaults to
+    /// the extension of the output file or table
+    #[structopt(short, long)]
+    pub format: Option<StatsFormat>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ImportArgs {
+    /// The csv file to import
+    pub input: PathBuf,
+    /// The app the file was exported from, options include strong,hevy,fitnotes,csv
+    /// defaults to working it out from the column names
+    #[structopt(long)]
+    pub from: Option<Source>,
+    /// A path to your profile, the sessions are added to the training log in it
+    #[structopt(short, long)]
+    pub profile: Option<PathBuf>,
+    /// A path to the training log to add the sessions to, instead of the one in your profile
+    #[structopt(short, long, required_unless = "profile")]
+    pub log: Option<PathBuf>,
+    /// A path to a .toml, .json or .yaml file of exercise names and the lift they are,
+    /// for example "Paused Bench" = "bench", these are added to the built in names
+    #[structopt(short, long)]
+    pub aliases: Option<PathBuf>,
+    /// The unit the weights are written to the log in, options include lb,kg
+    #[structopt(long, default_value = "lb")]
+    pub unit: Unit,
+    /// The unit of the weights in the file when it doesn't say, defaults to --unit
+    #[structopt(long)]
+    pub input_unit: Option<Unit>,
+    /// The personal records file to update with the imported sessions, defaults to the
+    /// records in your profile
+    #[structopt(short, long)]
+    pub records: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SuggestArgs {
+    /// A recent top set formatted as <lift>=<weight>x<reps> for example bench=225x5,
+    /// this can be repeated for each lift and replaces the log for that lift
+    #[structopt(short, long)]
+    pub set: Vec<LiftSet>,
+    /// A path to your profile, the top sets are found in its training log
+    /// and the lifts are rounded the same way
+    #[structopt(short, long)]
+    pub profile: Option<PathBuf>,
+    /// A path to the training log to find the top sets in, instead of the one in your profile
+    #[structopt(short, long)]
+    pub log: Option<PathBuf>,
+    /// How many weeks back from the last session in the log to look for top sets
+    #[structopt(long, default_value = "8")]
+    pub weeks: u32,
+    /// The percent of each estimated 1 rep max to use as the training max, 85 or 90 are common
+    #[structopt(long, default_value = "90")]
+    pub percent: f32,
+    /// Save the suggested training maxes to your profile
+    #[structopt(short, long, requires = "profile")]
+    pub write: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ReportArgs {
+    /// A path to your profile, the training log in it is used
+    #[structopt(short, long)]
+    pub profile: Option<PathBuf>,
+    /// A path to the training log, instead of the one in your profile
+    #[structopt(short, long, required_unless = "profile")]
+    pub log: Option<PathBuf>,
+    /// The path of the html file you'd like the report saved to
+    #[structopt(short, long)]
+    pub output: PathBuf,
+    /// The heading at the top of the report, for example the athlete's name
+    #[structopt(long, default_value = "Training report")]
+    pub title: String,
+    /// Flag a lift as stalled when its estimated 1 rep max hasn't gone up in this many cycles
+    #[structopt(long, default_value = "2")]
+    pub stall_cycles: u32,
+    /// Your bodyweight, instead of the latest one in your log or profile
+    #[structopt(long)]
+    pub bodyweight: Option<Weight>,
+    /// Score your total with the coefficients for male or female lifters, instead of the sex in your profile
+    #[structopt(long)]
+    pub sex: Option<Sex>,
+    /// The unit your log is in, options include lb,kg, instead of the unit in your profile
+    #[structopt(long)]
+    pub unit: Option<Unit>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BodyweightArgs {
+    /// Your bodyweight, the bodyweights recorded are listed when it isn't provided
+    pub weight: Option<Weight>,
+    /// A path to your profile, the bodyweight is recorded in its training log
+    #[structopt(short, long)]
+    pub profile: Option<PathBuf>,
+    /// A path to the training log, instead of the one in your profile
+    #[structopt(short, long, required_unless = "profile")]
+    pub log: Option<PathBuf>,
+    /// The day the bodyweight was taken, formatted as YYYY-MM-DD, defaults to today
+    #[structopt(long)]
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum StatsFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for StatsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "table" => Self::Table,
+            "csv" => Self::Csv,
+            "json" => Self::Json,
+            _ => return Err(format!("Unknown stats format: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ConvertTo {
+    Inventory,
+    Weights,
+}
+
+impl FromStr for ConvertTo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "inventory" => Self::Inventory,
+            "weights" => Self::Weights,
+            _ => return Err(format!("Unknown conversion: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Auto only uses colours when printing to a terminal and `NO_COLOR` isn't set
+    pub fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            Self::Auto => is_terminal && std::env::var_os("NO_COLOR").is_none(),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "auto" => Self::Auto,
+            "always" => Self::Always,
+            "never" => Self::Never,
+            _ => return Err(format!("Unknown color option: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+#[derive(Debug)]
+pub enum WeightsFormat {
+    Toml,
+    Json,
+    Yaml,
+    Text,
+}
+
+impl FromStr for WeightsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "toml" => Self::Toml,
+            "json" => Self::Json,
+            "yaml" => Self::Yaml,
+            "text" => Self::Text,
+            _ => return Err(format!("Unknown output format: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+/// A value for one of the lifts, parsed from `<lift>=<value>`
+#[derive(Debug)]
+pub struct LiftValue<T = f32> {
+    pub lift: Lift,
+    pub value: T,
+}
+
+impl<T: FromStr> FromStr for LiftValue<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '=');
+        let lift = parts.next().unwrap_or_default().trim().parse()?;
+        let value = parts
+            .next()
+            .and_then(|value| value.trim().parse().ok())
+            .ok_or_else(|| format!("Expected <lift>=<weight>: {:?}", s))?;
+        Ok(Self { lift, value })
+    }
+}
+
+/// What the maxes passed to `generate` are
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxInput {
+    OneRepMax,
+    TrainingMax,
+}
+
+impl FromStr for MaxInput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "one-rep-max" | "1rm" => Self::OneRepMax,
+            "training-max" | "tm" => Self::TrainingMax,
+            _ => return Err(format!("Unknown input: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+/// A percent for every lift, or for one lift when parsed from `<lift>=<percent>`
+#[derive(Debug)]
+pub struct PercentArg {
+    pub lift: Option<Lift>,
+    pub value: f32,
+}
+
+impl FromStr for PercentArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains('=') {
+            let value = s
+                .trim()
+                .parse()
+                .map_err(|_| format!("Expected <percent> or <lift>=<percent>: {:?}", s))?;
+            return Ok(Self { lift: None, value });
+        }
+        let LiftValue::<f32> { lift, value } = s.parse()?;
+        Ok(Self {
+            lift: Some(lift),
+            value,
+        })
+    }
+}
+
+/// A set of one of the lifts, parsed from `<lift>=<weight>x<reps>`
+#[derive(Debug)]
+pub struct LiftSet {
+    pub lift: Lift,
+    pub weight: Weight,
+    pub reps: u8,
+}
+
+impl FromStr for LiftSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Expected <lift>=<weight>x<reps>: {:?}", s);
+        let mut parts = s.splitn(2, '=');
+        let lift = parts.next().unwrap_or_default().trim().parse()?;
+        let mut set = parts.next().ok_or_else(invalid)?.splitn(2, 'x');
+        let weight = set
+            .next()
+            .and_then(|weight| weight.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        let reps = set
+            .next()
+            .and_then(|reps| reps.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        Ok(Self { lift, weight, reps })
+    }
+}
+
+#[derive(Debug)]
+pub struct WeightArg {
+    pub weight: Weight,
+    pub count: u8,
+}
+
+impl FromStr for WeightArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('x');
+        let weight = parts
+            .next()
+            .ok_or_else(|| format!("Invalid weight expected at least one value {:?}", s))?;
+        let parsed: Weight = weight
+            .parse()
+            .ok()
+            .filter(|weight: &Weight| weight.is_positive())
+            .ok_or_else(|| format!("Weight must be a positive number: {:?}", s))?;
+        let count: u8 = if let Some(count) = parts.next() {
+            if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .ok()
+                    .filter(|count| *count > 0)
+                    .ok_or_else(|| format!("Count must be a positive number: {:?}", s))?
+            }
+        } else {
+            1
+        };
+        Ok(Self {
+            weight: parsed,
+            count,
+        })
+    }
+}
@@ -0,0 +1,651 @@
+//! Runs each of the binary's subcommands with their parsed arguments
+
+use std::{
+    collections::HashMap,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::Local;
+
+use five_three_one::{
+    error,
+    file::{self, Format},
+    import::{self, Aliases, Unit},
+    inventory::Inventory,
+    loading::LoadingPlanner,
+    log::Log,
+    plan::{
+        one_rep_max, round_weight, Lift, PerLift, Program, TrainingMax, WarmUp, DEFAULT_ROUNDING,
+        DEFAULT_TRAINING_MAX_PERCENT,
+    },
+    plates::{calculate_all_weights_from, default_weights, PlateInventory, WeightsMap},
+    profile::Profile,
+    records::Records,
+    render::{self, RenderOptions},
+    report::{self, Athlete},
+    session::Session,
+    stats::Stats,
+    suggest::Suggestion,
+    supports::Supports,
+    terminal,
+    tui::Tui,
+    validate::Problems,
+    weight::Weight,
+};
+
+use crate::cli::{
+    BodyweightArgs, ConvertArgs, ConvertTo, GenerateArgs, ImportArgs, LiftValue, MaxInput,
+    OneRepArgs, ReportArgs, SessionArgs, StatsArgs, StatsFormat, SuggestArgs, WeightComboArgs,
+    WeightsFormat,
+};
+
+pub fn weight_combos(combos: WeightComboArgs) -> error::Result<()> {
+    let results = if let Some(path) = &combos.inventory {
+        let inventory = Inventory::from_path(path)?;
+        let bar = inventory.bar(combos.bar.as_deref())?;
+        inventory.plate_inventory(bar).weights_map()
+    } else {
+        let mut map = HashMap::new();
+        for combo in combos.weights {
+            map.entry(combo.weight)
+                .and_modify(|i| *i += combo.count)
+                .or_insert(combo.count);
+        }
+        calculate_all_weights_from(&map)
+    };
+    let format = combos.format.unwrap_or(WeightsFormat::Toml);
+    let rekeyed = sorted_weights(&results);
+    let text = match format {
+        WeightsFormat::Toml => Format::Toml.to_string(&rekeyed)?,
+        WeightsFormat::Json => Format::Json.to_string(&rekeyed)?,
+        WeightsFormat::Yaml => Format::Yaml.to_string(&rekeyed)?,
+        WeightsFormat::Text => {
+            let mut s = String::new();
+            for (key, value) in rekeyed {
+                s.push_str(&format!("{: >3} {:?}\n", key, value));
+            }
+            s
+        }
+    };
+    if let Some(path) = combos.output {
+        file::write(&path, text)?;
+    } else {
+        println!("{}", text);
+    }
+    Ok(())
+}
+
+/// The weights file with the keys sorted lightest first, so it's easier to read
+fn sorted_weights(weights: &WeightsMap) -> indexmap::IndexMap<String, Vec<Weight>> {
+    let mut keys: Vec<Weight> = weights.0.keys().copied().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| (key.to_string(), weights.0[&key].clone()))
+        .collect()
+}
+
+pub fn convert(args: ConvertArgs) -> error::Result<()> {
+    let format = match (args.format, &args.output) {
+        (Some(format), _) => format,
+        (None, Some(path)) => Format::of(path)?,
+        (None, None) => Format::Toml,
+    };
+    let text = match args.to {
+        ConvertTo::Inventory => {
+            let weights = WeightsMap::from_path(&args.input)?;
+            format.to_string(&Inventory::from_weights_map(&weights))?
+        }
+        ConvertTo::Weights => {
+            let inventory = Inventory::from_path(&args.input)?;
+            let bar = inventory.bar(args.bar.as_deref())?;
+            format.to_string(&sorted_weights(
+                &inventory.plate_inventory(bar).weights_map(),
+            ))?
+        }
+    };
+    if let Some(path) = args.output {
+        file::write(&path, text)?;
+    } else {
+        println!("{}", text);
+    }
+    Ok(())
+}
+
+pub fn one_rep(args: OneRepArgs) -> error::Result<()> {
+    let OneRepArgs { weight, reps } = args;
+    let mut problems = Problems::new();
+    if !(weight.is_finite() && weight > 0.0) {
+        problems.error(format!(
+            "The weight must be a positive number, found {}",
+            weight
+        ));
+    }
+    if reps == 0 {
+        problems.error("At least 1 rep must be done");
+    }
+    problems.finish()?;
+    let rounded = round_weight(one_rep_max(weight, reps));
+    println!("{}", rounded);
+    Ok(())
+}
+
+pub fn generate(gen_args: GenerateArgs) -> error::Result<()> {
+    let mut problems = Problems::new();
+    problems.max(Lift::Squat, gen_args.squat_max);
+    problems.max(Lift::Dead, gen_args.dead_max);
+    problems.max(Lift::Bench, gen_args.bench_max);
+    problems.max(Lift::Ohp, gen_args.ohp_max);
+    problems.months(gen_args.months);
+    problems.days_per_page(gen_args.days_per_page);
+    problems.columns(gen_args.columns);
+    let warm_up = if gen_args.no_warm_up {
+        WarmUp::none()
+    } else {
+        gen_args.warm_up.clone()
+    };
+    let mut inventory = read_plates(&gen_args, &mut problems)?;
+    if let Some(collar) = gen_args.collar {
+        inventory = inventory.with_collar(collar);
+    }
+    problems.max_imbalance(gen_args.max_imbalance);
+    let bar = inventory.empty();
+    problems.inventory(&inventory);
+    let planner = LoadingPlanner::new(inventory).with_max_imbalance(gen_args.max_imbalance);
+    let mut rounding = PerLift::all(DEFAULT_ROUNDING);
+    for LiftValue { lift, value } in &gen_args.rounding {
+        rounding.set(*lift, problems.rounding(*lift, *value, &planner));
+    }
+    let maxes = PerLift {
+        squat: gen_args.squat_max,
+        dead: gen_args.dead_max,
+        bench: gen_args.bench_max,
+        ohp: gen_args.ohp_max,
+    };
+    let training_max = match gen_args.input {
+        MaxInput::TrainingMax => {
+            if !gen_args.tm_percent.is_empty() {
+                problems.warn("--tm-percent is ignored when the maxes are already training maxes");
+            }
+            TrainingMax::new(
+                maxes.squat.into(),
+                maxes.dead.into(),
+                maxes.bench.into(),
+                maxes.ohp.into(),
+            )
+        }
+        MaxInput::OneRepMax => {
+            // a percent for every lift comes first, so one for a single lift always replaces it
+            let mut percent = PerLift::all(DEFAULT_TRAINING_MAX_PERCENT);
+            let (every, single): (Vec<_>, Vec<_>) =
+                gen_args.tm_percent.iter().partition(|p| p.lift.is_none());
+            for arg in every.into_iter().chain(single) {
+                problems.training_max_percent(arg.lift, arg.value);
+                match arg.lift {
+                    Some(lift) => percent.set(lift, arg.value),
+                    None => percent = PerLift::all(arg.value),
+                }
+            }
+            TrainingMax::from_percent_of(&maxes, &percent, &rounding)
+        }
+    };
+    problems.training_max(&training_max, bar);
+    let mut builder = Program::builder(training_max)
+        .cycles(gen_args.months)
+        .warm_up(warm_up)
+        .bar(bar);
+    if let Some(path) = &gen_args.records {
+        builder = builder.records(or_default_if_lenient(file::read(path), gen_args.lenient)?);
+    }
+    for &lift in &Lift::ALL {
+        builder = builder.rounding(lift, rounding.get(lift));
+    }
+    for LiftValue { lift, value } in &gen_args.increment {
+        problems.increment(*lift, *value);
+        builder = builder.increment(*lift, *value);
+    }
+    let program = builder.clone().build();
+    if let Some(bodyweight) = gen_args.bodyweight {
+        problems.bodyweight(bodyweight);
+    }
+    let supports = read_supports(gen_args.extra_path.clone(), gen_args.lenient)?
+        .with_bodyweight(gen_args.bodyweight);
+    problems.program(&program, &supports, &planner);
+    problems.assistance(&supports, &program);
+    problems.conditioning(&supports, &program);
+    for warning in problems.finish()? {
+        eprintln!("Warning: {}", warning);
+    }
+    if gen_args.interactive || gen_args.file.is_some() {
+        let options = RenderOptions {
+            diagrams: if gen_args.plate_diagrams {
+                Some(gen_args.plate_colors)
+            } else {
+                None
+            },
+            changes: gen_args.plate_diagrams || gen_args.plate_changes,
+            page_size: gen_args.page_size,
+            days_per_page: gen_args.days_per_page,
+            ..Default::default()
+        };
+        if let Some(html_path) = &gen_args.file {
+            let out = render::html(&program, &supports, &planner, options)?;
+            file::write(html_path, out)?;
+        } else {
+            Tui::new(builder, training_max, rounding, planner)
+                .with_supports(supports)
+                .with_options(options)
+                .run()?;
+        }
+    } else {
+        let is_terminal = io::stdout().is_terminal();
+        let options = RenderOptions {
+            changes: gen_args.plate_changes,
+            columns: gen_args.columns,
+            width: if is_terminal {
+                crossterm::terminal::size()
+                    .ok()
+                    .map(|(width, _)| width as usize)
+            } else {
+                None
+            },
+            color: gen_args.color.enabled(is_terminal),
+            ..Default::default()
+        };
+        print!(
+            "{}",
+            terminal::render(&program, &supports, &planner, options)
+        )
+    }
+    Ok(())
+}
+
+pub fn session(args: SessionArgs) -> error::Result<()> {
+    let mut profile = Profile::from_path(&args.profile)?;
+    let log_path = args.log.unwrap_or_else(|| profile.log.clone());
+    let mut log = Log::from_path(&log_path)?;
+    let records_path = args.records.unwrap_or_else(|| profile.records.clone());
+    let mut records = Records::from_path(&records_path)?;
+    let mut problems = Problems::new();
+    problems.schedule(&profile.schedule);
+    for &lift in &Lift::ALL {
+        problems.max(lift, profile.training_max.get(lift).into());
+    }
+    for (lift, value) in &profile.increments {
+        problems.increment(*lift, *value);
+    }
+    let inventory = profile.plates()?;
+    let bar = inventory.empty();
+    problems.training_max(&profile.training_max, bar);
+    problems.inventory(&inventory);
+    let planner = LoadingPlanner::new(inventory);
+    for (lift, value) in profile.rounding.iter_mut() {
+        *value = problems.rounding(*lift, *value, &planner);
+    }
+    let date = args.date.unwrap_or_else(|| Local::today().naive_local());
+    problems.exercises(
+        &profile
+            .supports()?
+            .with_bodyweight(profile.bodyweight(&log, date)),
+    );
+    for warning in problems.finish()? {
+        eprintln!("Warning: {}", warning);
+    }
+    let mut session = match Session::scheduled(&profile, &log, date, bar)? {
+        Some(session) => session,
+        None => {
+            let next = match profile.next_session(date) {
+                Some(next) => next,
+                None => return Ok(()),
+            };
+            let lift = Session::scheduled(&profile, &log, next, bar)?
+                .map(|session| session.lift.name())
+                .unwrap_or_default();
+            println!(
+                "Nothing is scheduled for {}, the next session is {} on {}",
+                date.format("%A %-d %B %Y"),
+                lift,
+                next.format("%A %-d %B %Y")
+            );
+            return Ok(());
+        }
+    };
+    if log.on(date, session.lift).next().is_some() {
+        eprintln!(
+            "Warning: {} has already been logged for {}",
+            session.lift.name(),
+            date
+        );
+    }
+    records.apply(&mut session.week);
+    match session.run(&planner, args.rest.map(Duration::from_secs))? {
+        Some(logged) => {
+            let broken = records.update(&logged);
+            log.push(logged);
+            log.write(&log_path)?;
+            println!("Logged {} to {}", session.lift.name(), log_path.display());
+            if !broken.is_empty() {
+                records.write(&records_path)?;
+                for record in broken {
+                    println!("{}", record);
+                }
+            }
+        }
+        None => println!("Stopped, nothing was logged"),
+    }
+    Ok(())
+}
+
+pub fn stats(args: StatsArgs) -> error::Result<()> {
+    let mut problems = Problems::new();
+    problems.stall_cycles(args.stall_cycles);
+    problems.finish()?;
+    let log = read_log(args.log, args.profile.as_deref())?;
+    let stats = Stats::new(&log, args.by, args.stall_cycles);
+    let extension = args
+        .output
+        .as_ref()
+        .and_then(|path| path.extension())
+        .and_then(|ext| ext.to_str());
+    let format = match (args.format, extension) {
+        (Some(format), _) => format,
+        (None, Some("csv")) => StatsFormat::Csv,
+        (None, Some("json")) => StatsFormat::Json,
+        (None, _) => StatsFormat::Table,
+    };
+    let text = match format {
+        StatsFormat::Table => stats.table(),
+        StatsFormat::Csv => stats.csv(),
+        StatsFormat::Json => Format::Json.to_string(&stats)?,
+    };
+    if let Some(path) = args.output {
+        file::write(&path, text)?;
+    } else {
+        print!("{}", text);
+    }
+    Ok(())
+}
+
+pub fn import(args: ImportArgs) -> error::Result<()> {
+    let aliases = match &args.aliases {
+        Some(path) => Aliases::from_path(path)?,
+        None => Aliases::default(),
+    };
+    let imported = import::read(
+        &args.input,
+        args.from,
+        &aliases,
+        args.input_unit.unwrap_or(args.unit),
+        args.unit,
+    )?;
+    let records_path = match (args.records, args.profile.as_deref()) {
+        (Some(path), _) => Some(path),
+        (None, Some(profile)) => Some(Profile::from_path(profile)?.records),
+        (None, None) => None,
+    };
+    let mut records = match &records_path {
+        Some(path) => Records::from_path(path)?,
+        None => Records::default(),
+    };
+    let log_path = log_path(args.log, args.profile.as_deref())?;
+    let mut log = Log::from_path(&log_path)?;
+    let (mut added, mut skipped, mut broken) = (0, 0, 0);
+    let mut sessions = imported.sessions;
+    // oldest first, so each record is dated the first time it was set
+    sessions.sort_by_key(|session| session.date);
+    for session in sessions {
+        if log.on(session.date, session.lift).next().is_some() {
+            skipped += 1;
+        } else {
+            broken += records.update(&session).len();
+            log.push(session);
+            added += 1;
+        }
+    }
+    log.write(&log_path)?;
+    if !imported.unknown.is_empty() {
+        let names: Vec<String> = imported
+            .unknown
+            .iter()
+            .map(|(name, sets)| match sets {
+                1 => format!("{} (1 set)", name),
+                _ => format!("{} ({} sets)", name, sets),
+            })
+            .collect();
+        eprintln!(
+            "Warning: These exercises aren't one of the main lifts and weren't imported, \
+            they can be added with --aliases: {}",
+            names.join(", ")
+        );
+    }
+    println!("Imported {} sessions to {}", added, log_path.display());
+    if skipped > 0 {
+        println!(
+            "Skipped {} sessions that were already in the log for the same day",
+            skipped
+        );
+    }
+    if let Some(path) = records_path.filter(|_| broken > 0) {
+        records.write(&path)?;
+        println!("Updated {} personal records in {}", broken, path.display());
+    }
+    let latest = match log.sessions.iter().map(|session| session.date).max() {
+        Some(latest) => latest,
+        None => return Ok(()),
+    };
+    let since = latest - chrono::Duration::weeks(SUGGESTION_WEEKS);
+    println!(
+        "\nSuggested training maxes, {}% of the best estimated 1 rep max from the {} weeks before {}",
+        DEFAULT_TRAINING_MAX_PERCENT,
+        SUGGESTION_WEEKS,
+        latest
+    );
+    for &lift in &Lift::ALL {
+        if let Some(suggestion) = Suggestion::from_log(
+            &log,
+            lift,
+            since,
+            DEFAULT_TRAINING_MAX_PERCENT,
+            DEFAULT_ROUNDING,
+        ) {
+            println!(
+                "  {} {}: {}",
+                lift.name(),
+                suggestion.training_max(),
+                suggestion.explain()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// How far back to look for top sets when suggesting training maxes after an import
+const SUGGESTION_WEEKS: i64 = 8;
+
+pub fn suggest(args: SuggestArgs) -> error::Result<()> {
+    let mut problems = Problems::new();
+    problems.training_max_percent(None, args.percent);
+    for set in &args.set {
+        problems.max(set.lift, set.weight.into());
+        if set.reps == 0 {
+            problems.error(format!(
+                "At least 1 rep must be done for {}",
+                set.lift.name()
+            ));
+        }
+    }
+    if args.set.is_empty() && args.log.is_none() && args.profile.is_none() {
+        problems
+            .error("Provide your top sets with --set, or a training log with --log or --profile");
+    }
+    problems.finish()?;
+    let profile = args
+        .profile
+        .as_deref()
+        .map(Profile::from_path)
+        .transpose()?;
+    let rounding = profile
+        .as_ref()
+        .map(Profile::rounding)
+        .unwrap_or_else(|| PerLift::all(DEFAULT_ROUNDING));
+    let log = match (&args.log, &profile) {
+        (Some(path), _) => Some(Log::from_path(path)?),
+        (None, Some(profile)) => Some(Log::from_path(&profile.log)?),
+        (None, None) => None,
+    };
+    let since = log
+        .as_ref()
+        .and_then(|log| log.sessions.iter().map(|session| session.date).max())
+        .map(|latest| latest - chrono::Duration::weeks(args.weeks as i64));
+    let mut suggestions = Vec::new();
+    for &lift in &Lift::ALL {
+        let suggestion = match args.set.iter().rfind(|set| set.lift == lift) {
+            Some(set) => Some(Suggestion::new(
+                lift,
+                set.weight,
+                set.reps,
+                args.percent,
+                rounding.get(lift),
+            )),
+            None => log.as_ref().zip(since).and_then(|(log, since)| {
+                Suggestion::from_log(log, lift, since, args.percent, rounding.get(lift))
+            }),
+        };
+        match suggestion {
+            Some(suggestion) => {
+                println!(
+                    "{} {}: {}",
+                    lift.name(),
+                    suggestion.training_max(),
+                    suggestion.explain()
+                );
+                suggestions.push((lift, suggestion.training_max()));
+            }
+            None => println!("{}: no top set to go from", lift.name()),
+        }
+    }
+    if let (true, Some(path)) = (args.write, &args.profile) {
+        Profile::save_training_maxes(path, &suggestions)?;
+        println!("Saved the training maxes to {}", path.display());
+    }
+    Ok(())
+}
+
+pub fn report(args: ReportArgs) -> error::Result<()> {
+    let mut problems = Problems::new();
+    problems.stall_cycles(args.stall_cycles);
+    if let Some(bodyweight) = args.bodyweight {
+        problems.bodyweight(bodyweight);
+    }
+    problems.finish()?;
+    let profile = args
+        .profile
+        .as_deref()
+        .map(Profile::from_path)
+        .transpose()?;
+    let log = match (args.log, &profile) {
+        (Some(path), _) => Log::from_path(&path)?,
+        (None, Some(profile)) => Log::from_path(&profile.log)?,
+        (None, None) => unreachable!("structopt requires a log or a profile"),
+    };
+    let today = Local::today().naive_local();
+    let athlete = Athlete {
+        bodyweight: args.bodyweight.or_else(|| match &profile {
+            Some(profile) => profile.bodyweight(&log, today),
+            None => log.bodyweight(today),
+        }),
+        sex: args.sex.or_else(|| profile.as_ref().and_then(|p| p.sex)),
+        unit: args
+            .unit
+            .or_else(|| profile.as_ref().and_then(|p| p.unit))
+            .unwrap_or(Unit::Lb),
+        training_max: profile.as_ref().map(|p| p.training_max),
+    };
+    let out = report::html(&log, &args.title, today, args.stall_cycles, &athlete)?;
+    file::write(&args.output, out)
+}
+
+pub fn bodyweight(args: BodyweightArgs) -> error::Result<()> {
+    let path = log_path(args.log, args.profile.as_deref())?;
+    let mut log = Log::from_path(&path)?;
+    let weight = match args.weight {
+        Some(weight) => weight,
+        None => {
+            if log.bodyweights.is_empty() {
+                println!("No bodyweights have been recorded in {}", path.display());
+            }
+            for weighing in &log.bodyweights {
+                println!("{} {}", weighing.date, weighing.weight);
+            }
+            return Ok(());
+        }
+    };
+    let mut problems = Problems::new();
+    problems.bodyweight(weight);
+    problems.finish()?;
+    let date = args.date.unwrap_or_else(|| Local::today().naive_local());
+    let previous = log
+        .bodyweights
+        .iter()
+        .rev()
+        .find(|weighing| weighing.date < date)
+        .copied();
+    log.record_bodyweight(date, weight);
+    log.write(&path)?;
+    print!("Recorded a bodyweight of {} on {}", weight, date);
+    match previous.map(|previous| (weight - previous.weight, previous.date)) {
+        Some((change, since)) if change < Weight::ZERO => {
+            println!(", {} since {}", change, since)
+        }
+        Some((change, since)) => println!(", +{} since {}", change, since),
+        None => println!(),
+    }
+    Ok(())
+}
+
+/// The training log at `log`, or the one in the `profile` when there isn't a path to one
+fn read_log(log: Option<PathBuf>, profile: Option<&Path>) -> error::Result<Log> {
+    Log::from_path(&log_path(log, profile)?)
+}
+
+fn log_path(log: Option<PathBuf>, profile: Option<&Path>) -> error::Result<PathBuf> {
+    Ok(match (log, profile) {
+        (Some(path), _) => path,
+        (None, Some(profile)) => Profile::from_path(profile)?.log,
+        (None, None) => unreachable!("structopt requires a log or a profile"),
+    })
+}
+
+/// The plates to plan with, from the inventory or weights file or the defaults
+fn read_plates(gen_args: &GenerateArgs, problems: &mut Problems) -> error::Result<PlateInventory> {
+    if let Some(path) = &gen_args.inventory {
+        let inventory = or_default_if_lenient(Inventory::from_path(path), gen_args.lenient)?;
+        problems.owned_plates(&inventory);
+        let bar = inventory.bar(gen_args.bar.as_deref())?;
+        return Ok(inventory.plate_inventory(bar));
+    }
+    let weights = match &gen_args.weights_path {
+        Some(path) => or_default_if_lenient(WeightsMap::from_path(path), gen_args.lenient)?,
+        None => default_weights(),
+    };
+    Ok(PlateInventory::from_weights_map(&weights))
+}
+
+fn read_supports(path: Option<PathBuf>, lenient: bool) -> error::Result<Supports> {
+    match path {
+        Some(path) => or_default_if_lenient(Supports::from_path(&path), lenient),
+        None => Ok(Supports::default()),
+    }
+}
+
+/// When `lenient` replace any error with the default value, warning that the default was used
+fn or_default_if_lenient<T: Default>(result: error::Result<T>, lenient: bool) -> error::Result<T> {
+    match result {
+        Err(e) if lenient => {
+            eprintln!("Warning: {}, using the defaults instead", e);
+            Ok(T::default())
+        }
+        result => result,
+    }
+}
@@ -0,0 +1,207 @@
+use std::str::FromStr;
+
+use crate::weight::Weight;
+
+/// The colours used to draw plates in the html plan's diagrams
+#[derive(Debug, Clone, Copy)]
+pub enum PlateColors {
+    /// Shades of grey, darker for heavier plates
+    Plain,
+    /// The IWF/IPF competition colours for kg plates
+    Iwf,
+}
+
+impl PlateColors {
+    /// The plate that should be drawn full height
+    fn largest_plate(self) -> f32 {
+        match self {
+            Self::Plain => 45.0,
+            Self::Iwf => 25.0,
+        }
+    }
+
+    /// The fill and stroke colours for a plate
+    fn color_of(self, plate: Weight) -> (&'static str, &'static str) {
+        match self {
+            Self::Plain => {
+                let relative = f32::from(plate) / self.largest_plate();
+                let fill = if relative >= 0.75 {
+                    "#222"
+                } else if relative >= 0.5 {
+                    "#444"
+                } else if relative >= 0.2 {
+                    "#666"
+                } else if relative >= 0.1 {
+                    "#888"
+                } else {
+                    "#aaa"
+                };
+                (fill, "#000")
+            }
+            Self::Iwf => {
+                let fill = match plate.thousandths() {
+                    25_000 | 2_500 => "#d32f2f",
+                    20_000 | 2_000 => "#1565c0",
+                    15_000 | 1_500 => "#f9a825",
+                    10_000 | 1_000 => "#2e7d32",
+                    5_000 | 500 => "#f5f5f5",
+                    _ => "#c0c0c0",
+                };
+                (fill, "#000")
+            }
+        }
+    }
+}
+
+impl FromStr for PlateColors {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "plain" => Self::Plain,
+            "iwf" | "ipf" => Self::Iwf,
+            _ => return Err(format!("Unknown plate colours: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+const DIAGRAM_WIDTH: f32 = 100.0;
+const DIAGRAM_HEIGHT: f32 = 40.0;
+const SLEEVE_START: f32 = 16.0;
+const PLATE_GAP: f32 = 0.5;
+const CLIP_WIDTH: f32 = 3.0;
+/// How much of the sleeve the plates can take up, leaving room for the clip
+const SLEEVE_ROOM: f32 = DIAGRAM_WIDTH - SLEEVE_START - PLATE_GAP - CLIP_WIDTH;
+
+/// Draw one side of a loaded bar as an svg, the plates are drawn from
+/// the collar outward in the order they should be loaded followed by a clip
+pub fn plate_diagram(plates: &[Weight], colors: PlateColors) -> String {
+    let mut s = svg_start(DIAGRAM_WIDTH);
+    s.push_str(&sleeve(plates, colors));
+    s.push_str("</svg>");
+    s
+}
+
+/// Draw both sides of a bar that isn't loaded evenly, the left
+/// sleeve is mirrored so the collars meet in the middle
+pub fn uneven_plate_diagram(left: &[Weight], right: &[Weight], colors: PlateColors) -> String {
+    let mut s = svg_start(DIAGRAM_WIDTH * 2.0);
+    s.push_str(&format!(
+        r#"<g transform="translate({} 0) scale(-1 1)">{}</g>"#,
+        DIAGRAM_WIDTH,
+        sleeve(left, colors)
+    ));
+    s.push_str(&format!(
+        r#"<g transform="translate({} 0)">{}</g>"#,
+        DIAGRAM_WIDTH,
+        sleeve(right, colors)
+    ));
+    s.push_str("</svg>");
+    s
+}
+
+fn svg_start(width: f32) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = DIAGRAM_HEIGHT,
+    )
+}
+
+/// The shapes for one sleeve of the bar, starting from the collar
+fn sleeve(plates: &[Weight], colors: PlateColors) -> String {
+    let mid = DIAGRAM_HEIGHT / 2.0;
+    let mut s = String::new();
+    s.push_str(&format!(
+        r##"<rect x="0" y="{}" width="{}" height="2" fill="#999"/>"##,
+        mid - 1.0,
+        SLEEVE_START,
+    ));
+    s.push_str(&format!(
+        r##"<rect x="{}" y="{}" width="{}" height="4" fill="#bbb"/>"##,
+        SLEEVE_START,
+        mid - 2.0,
+        DIAGRAM_WIDTH - SLEEVE_START,
+    ));
+    s.push_str(&format!(
+        r##"<rect x="{}" y="{}" width="3" height="14" fill="#555"/>"##,
+        SLEEVE_START - 3.0,
+        mid - 7.0,
+    ));
+    let relative = |plate: Weight| (f32::from(plate) / colors.largest_plate()).min(1.0).sqrt();
+    let width = |relative: f32| if relative > 0.5 { 6.0 } else { 4.0 };
+    // squeeze the plates together when they'd run past the end of the sleeve
+    let needed: f32 = plates
+        .iter()
+        .map(|plate| width(relative(*plate)) + PLATE_GAP)
+        .sum();
+    let scale = (SLEEVE_ROOM / needed).min(1.0);
+    let mut x = SLEEVE_START;
+    for plate in plates {
+        let (fill, stroke) = colors.color_of(*plate);
+        let relative = relative(*plate);
+        let height = 10.0 + (DIAGRAM_HEIGHT - 12.0) * relative;
+        let width = width(relative) * scale;
+        s.push_str(&format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="1" fill="{}" stroke="{}" stroke-width="0.5"><title>{}</title></rect>"#,
+            x,
+            mid - height / 2.0,
+            width,
+            height,
+            fill,
+            stroke,
+            plate,
+        ));
+        x += width + PLATE_GAP * scale;
+    }
+    s.push_str(&format!(
+        r##"<rect x="{}" y="{}" width="{}" height="8" fill="#222"><title>clip</title></rect>"##,
+        x + PLATE_GAP,
+        mid - 4.0,
+        CLIP_WIDTH,
+    ));
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The right hand edge of every rect in an svg
+    fn right_edges(svg: &str) -> Vec<f32> {
+        svg.split("<rect ")
+            .skip(1)
+            .map(|rect| format!(" {}", rect))
+            .map(|rect| {
+                let attr = |name: &str| -> f32 {
+                    let start = rect.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+                    let len = rect[start..].find('"').unwrap();
+                    rect[start..start + len].parse().unwrap()
+                };
+                attr("x") + attr("width")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_full_sleeve_fits_in_the_diagram() {
+        let plates = [
+            45.0, 45.0, 45.0, 45.0, 45.0, 45.0, 45.0, 45.0, 45.0, 45.0, 25.0, 10.0, 5.0, 2.5,
+        ];
+        let plates: Vec<Weight> = plates.iter().map(|&plate| Weight::from(plate)).collect();
+        for &colors in &[PlateColors::Plain, PlateColors::Iwf] {
+            let edges = right_edges(&plate_diagram(&plates, colors));
+            assert_eq!(edges.len(), 3 + plates.len() + 1);
+            for edge in edges {
+                assert!(edge <= DIAGRAM_WIDTH + 0.01, "{} is past the end", edge);
+            }
+        }
+    }
+
+    #[test]
+    fn a_light_sleeve_is_drawn_at_full_size() {
+        let svg = plate_diagram(&[Weight::from(45.0)], PlateColors::Plain);
+        assert!(svg.contains(r#"x="16" y="1" width="6""#));
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fmt, path::PathBuf};
+
+/// Everything that can go wrong reading inputs and generating a plan
+#[derive(Debug)]
+pub enum Error {
+    /// A file couldn't be read
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A file couldn't be written
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A file's contents couldn't be parsed, line and column start at 1
+    Parse {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    /// A file doesn't have a .toml, .json or .yaml extension
+    UnknownFormat { path: PathBuf },
+    /// The inputs were read but can't be used, with every problem found
+    Validation(Vec<String>),
+    /// Something couldn't be converted to toml, json or yaml
+    Serialize(String),
+    /// The html template for a plan or report failed to render
+    Template(tera::Error),
+    /// The interactive view couldn't read from or draw to the terminal
+    Terminal(std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read { path, source } => {
+                write!(f, "Unable to read {}: {}", path.display(), source)
+            }
+            Self::Write { path, source } => {
+                write!(f, "Unable to write {}: {}", path.display(), source)
+            }
+            Self::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "Invalid file {}", path.display())?;
+                if let Some(line) = line {
+                    write!(f, ":{}", line)?;
+                    if let Some(column) = column {
+                        write!(f, ":{}", column)?;
+                    }
+                }
+                write!(f, ": {}", message)
+            }
+            Self::UnknownFormat { path } => write!(
+                f,
+                "Unknown file type {}, expected a .toml, .json or .yaml file",
+                path.display()
+            ),
+            Self::Validation(problems) => {
+                write!(f, "Invalid input")?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            Self::Serialize(message) => write!(f, "Unable to serialize: {}", message),
+            Self::Template(e) => {
+                write!(f, "Unable to render the html: {}", e)?;
+                let mut source = std::error::Error::source(e);
+                while let Some(inner) = source {
+                    write!(f, ": {}", inner)?;
+                    source = inner.source();
+                }
+                Ok(())
+            }
+            Self::Terminal(e) => write!(f, "Unable to use the terminal: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read { source, .. } | Self::Write { source, .. } | Self::Terminal(source) => {
+                Some(source)
+            }
+            Self::Template(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<tera::Error> for Error {
+    fn from(e: tera::Error) -> Self {
+        Self::Template(e)
+    }
+}
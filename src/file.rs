@@ -0,0 +1,128 @@
+use std::{path::Path, str::FromStr};
+
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+
+use crate::error::{Error, Result};
+
+/// The formats input and output files can be written in
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl Format {
+    /// The format for a path, based on its extension
+    pub fn of(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            Some("yaml") | Some("yml") => Ok(Self::Yaml),
+            _ => Err(Error::UnknownFormat {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+
+    /// Parse `raw` as this format, `path` is only used for errors
+    pub fn parse<T: DeserializeOwned>(self, raw: &str, path: &Path) -> Result<T> {
+        let parse_error = |line, column, message: String| Error::Parse {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message: without_location(&message).to_string(),
+        };
+        match self {
+            Self::Toml => toml::from_str(raw).map_err(|e| {
+                let (line, column) = e
+                    .line_col()
+                    .map(|(line, column)| (Some(line + 1), Some(column + 1)))
+                    .unwrap_or_default();
+                parse_error(line, column, e.to_string())
+            }),
+            Self::Json => serde_json::from_str(raw)
+                .map_err(|e| parse_error(Some(e.line()), Some(e.column()), e.to_string())),
+            Self::Yaml => serde_yaml::from_str(raw).map_err(|e| {
+                let location = e.location();
+                parse_error(
+                    location.as_ref().map(|l| l.line()),
+                    location.as_ref().map(|l| l.column()),
+                    e.to_string(),
+                )
+            }),
+        }
+    }
+
+    pub fn to_string<T: Serialize>(self, value: &T) -> Result<String> {
+        match self {
+            Self::Toml => {
+                toml::to_string_pretty(value).map_err(|e| Error::Serialize(e.to_string()))
+            }
+            Self::Json => {
+                serde_json::to_string_pretty(value).map_err(|e| Error::Serialize(e.to_string()))
+            }
+            Self::Yaml => serde_yaml::to_string(value).map_err(|e| Error::Serialize(e.to_string())),
+        }
+    }
+}
+
+/// The parsers end their messages with the location, which is already
+/// included in `Error::Parse` so it is removed
+fn without_location(message: &str) -> &str {
+    match message.rfind(" at line ") {
+        Some(idx) => &message[..idx],
+        None => message,
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "toml" => Self::Toml,
+            "json" => Self::Json,
+            "yaml" | "yml" => Self::Yaml,
+            _ => return Err(format!("Unknown file format: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+/// Read and parse a .toml, .json or .yaml file
+pub fn read<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let format = Format::of(path)?;
+    let raw = std::fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    format.parse(&raw, path)
+}
+
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    std::fs::write(path, contents).map_err(|source| Error::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Deserialize a date from a YYYY-MM-DD string or a toml date, for `#[serde(deserialize_with)]`
+pub fn date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<NaiveDate, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawDate {
+        Text(String),
+        Toml(toml::value::Datetime),
+    }
+    let text = match RawDate::deserialize(deserializer)? {
+        RawDate::Text(text) => text,
+        RawDate::Toml(date) => date.to_string(),
+    };
+    text.parse().map_err(|_| {
+        serde::de::Error::custom(format!("Invalid date {:?}, expected YYYY-MM-DD", text))
+    })
+}
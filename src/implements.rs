@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::weight::Weight;
+
+/// Something other than a barbell used for supporting work
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Implement {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: ImplementKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ImplementKind {
+    /// A rack of fixed weights, like pairs of dumbbells or kettlebells
+    Fixed {
+        #[serde(default)]
+        weights: Vec<Weight>,
+        /// An evenly spaced rack of weights, for example 5-50 by 5
+        #[serde(default)]
+        range: Option<WeightRange>,
+    },
+    /// Handles that are loaded with plates on each end, like adjustable dumbbells
+    Adjustable {
+        handle: Weight,
+        /// How many handles the plates are shared between, 2 for a pair of dumbbells
+        #[serde(default = "default_handles")]
+        handles: u8,
+        /// All of the plates for these handles, with the total number of each
+        plates: Vec<PlateCount>,
+    },
+}
+
+fn default_handles() -> u8 {
+    2
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct WeightRange {
+    pub start: Weight,
+    pub end: Weight,
+    pub step: Weight,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct PlateCount {
+    pub weight: Weight,
+    pub count: u8,
+}
+
+impl Implement {
+    /// Every weight this implement can be set to, lightest first
+    pub fn achievable_weights(&self) -> Vec<Weight> {
+        let mut ret: Vec<Weight> = match &self.kind {
+            ImplementKind::Fixed { weights, range } => {
+                let mut ret = weights.clone();
+                if let Some(range) = range.filter(|r| r.step.is_positive()) {
+                    let mut weight = range.start;
+                    while weight <= range.end {
+                        ret.push(weight);
+                        weight += range.step;
+                    }
+                }
+                ret
+            }
+            ImplementKind::Adjustable {
+                handle,
+                handles,
+                plates,
+            } => {
+                // both ends of a handle are loaded the same, so each plate
+                // is split between every end of every handle
+                let ends = (*handles).max(1) * 2;
+                let mut sides = vec![Weight::ZERO];
+                for plate in plates {
+                    let per_end = plate.count / ends;
+                    sides = sides
+                        .iter()
+                        .flat_map(|side| {
+                            (0..=per_end).map(move |ct| *side + plate.weight * ct as u32)
+                        })
+                        .collect();
+                }
+                sides.into_iter().map(|side| *handle + side * 2).collect()
+            }
+        };
+        ret.sort();
+        ret.dedup();
+        ret
+    }
+
+    /// The achievable weight closest to the one provided, preferring the lighter of two
+    pub fn closest_weight(&self, weight: Weight) -> Option<Weight> {
+        let distance = |w: Weight| (w - weight).thousandths().abs();
+        self.achievable_weights()
+            .into_iter()
+            .fold(None, |closest, w| match closest {
+                Some(c) if distance(c) <= distance(w) => Some(c),
+                _ => Some(w),
+            })
+    }
+}
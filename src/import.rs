@@ -0,0 +1,580 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file,
+    log::{LoggedSession, LoggedSet},
+    plan::Lift,
+    weight::Weight,
+};
+
+/// The app a csv file was exported from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Strong,
+    Hevy,
+    FitNotes,
+    /// A spreadsheet with date, exercise, weight and reps columns
+    Csv,
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "strong" => Self::Strong,
+            "hevy" => Self::Hevy,
+            "fitnotes" => Self::FitNotes,
+            "csv" => Self::Csv,
+            _ => return Err(format!("Unknown import format: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+impl Source {
+    /// Work out which app exported a file from its header row
+    pub fn detect(headers: &csv::StringRecord) -> Self {
+        let has = |name: &str| headers.iter().any(|h| h.eq_ignore_ascii_case(name));
+        if has("exercise_title") {
+            Self::Hevy
+        } else if has("exercise name") && has("set order") {
+            Self::Strong
+        } else if has("exercise") && has("category") {
+            Self::FitNotes
+        } else {
+            Self::Csv
+        }
+    }
+
+    fn date_columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Hevy => &["start_time"],
+            _ => &["date"],
+        }
+    }
+
+    fn exercise_columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Strong => &["exercise name"],
+            Self::Hevy => &["exercise_title"],
+            Self::FitNotes => &["exercise"],
+            Self::Csv => &["exercise", "lift"],
+        }
+    }
+
+    /// The column that marks warm up sets, FitNotes doesn't have one
+    fn warm_up_columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Strong => &["set order"],
+            Self::Hevy => &["set_type"],
+            Self::FitNotes => &[],
+            Self::Csv => &["warm_up", "warm up", "warmup"],
+        }
+    }
+
+    fn is_warm_up(self, value: &str) -> bool {
+        let value = value.trim().to_lowercase();
+        match self {
+            Self::Strong => value == "w",
+            Self::Hevy => value == "warmup",
+            Self::FitNotes => false,
+            Self::Csv => matches!(value.as_str(), "true" | "yes" | "y" | "1" | "w"),
+        }
+    }
+}
+
+/// The unit a weight is in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    Lb,
+    Kg,
+}
+
+const LB_PER_KG: f32 = 2.204_622_6;
+
+impl FromStr for Unit {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let ret = match s.trim().to_lowercase().as_str() {
+            "lb" | "lbs" | "pounds" => Self::Lb,
+            "kg" | "kgs" | "kilograms" => Self::Kg,
+            _ => return Err(format!("Unknown unit: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+impl Unit {
+    /// Convert `weight` from this unit to the `other`, to the nearest hundredth
+    pub fn convert(self, weight: Weight, other: Unit) -> Weight {
+        let thousandths = weight.thousandths() as f64;
+        let converted = match (self, other) {
+            (Self::Kg, Self::Lb) => thousandths * LB_PER_KG as f64,
+            (Self::Lb, Self::Kg) => thousandths / LB_PER_KG as f64,
+            _ => return weight,
+        };
+        Weight::from_thousandths((converted / 10.0).round() as i64 * 10)
+    }
+
+    /// The abbreviation shown after a weight
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Lb => "lb",
+            Self::Kg => "kg",
+        }
+    }
+
+    /// The unit named in a column header, for example `weight_kg` or `Weight (lbs)`
+    fn of_header(header: &str) -> Option<Self> {
+        let header = header.to_lowercase();
+        if header.contains("kg") {
+            Some(Self::Kg)
+        } else if header.contains("lb") {
+            Some(Self::Lb)
+        } else {
+            None
+        }
+    }
+}
+
+/// The exercise names that are imported as each of the main lifts, matched ignoring case
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Aliases(pub BTreeMap<String, Lift>);
+
+impl Default for Aliases {
+    /// The names Strong, Hevy and FitNotes use for the barbell versions of the main lifts
+    fn default() -> Self {
+        let names: &[(&str, Lift)] = &[
+            ("Bench Press (Barbell)", Lift::Bench),
+            ("Flat Barbell Bench Press", Lift::Bench),
+            ("Barbell Bench Press", Lift::Bench),
+            ("Bench Press", Lift::Bench),
+            ("Squat (Barbell)", Lift::Squat),
+            ("Barbell Squat", Lift::Squat),
+            ("Back Squat", Lift::Squat),
+            ("Squat", Lift::Squat),
+            ("Overhead Press (Barbell)", Lift::Ohp),
+            ("Standing Barbell Shoulder Press (OHP)", Lift::Ohp),
+            ("Overhead Press", Lift::Ohp),
+            ("Military Press", Lift::Ohp),
+            ("Strict Press (Barbell)", Lift::Ohp),
+            ("OHP", Lift::Ohp),
+            ("Deadlift (Barbell)", Lift::Dead),
+            ("Barbell Deadlift", Lift::Dead),
+            ("Conventional Deadlift", Lift::Dead),
+            ("Deadlift", Lift::Dead),
+        ];
+        Self(
+            names
+                .iter()
+                .map(|(name, lift)| (name.to_string(), *lift))
+                .collect(),
+        )
+    }
+}
+
+impl Aliases {
+    /// The default aliases with the ones in a .toml, .json or .yaml file added,
+    /// a name in the file replaces the default for the same name
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let mut aliases = Self::default();
+        let added: Self = file::read(path)?;
+        for (name, lift) in added.0 {
+            aliases
+                .0
+                .retain(|existing, _| !existing.eq_ignore_ascii_case(&name));
+            aliases.0.insert(name, lift);
+        }
+        Ok(aliases)
+    }
+
+    pub fn lift(&self, exercise: &str) -> Option<Lift> {
+        let exercise = exercise.trim();
+        self.0
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(exercise))
+            .map(|(_, lift)| *lift)
+    }
+}
+
+/// The sessions read from an export, grouped by day and lift
+#[derive(Debug, Clone, Default)]
+pub struct Imported {
+    pub sessions: Vec<LoggedSession>,
+    /// The number of sets skipped for each exercise that isn't one of the main lifts
+    pub unknown: BTreeMap<String, usize>,
+}
+
+/// Read the sets of the main lifts from a csv export, converting every weight to `unit`
+///
+/// The `source` is worked out from the header row when it isn't provided, and when
+/// the file doesn't say what unit its weights are in they're taken to be `input_unit`.
+pub fn read(
+    path: &Path,
+    source: Option<Source>,
+    aliases: &Aliases,
+    input_unit: Unit,
+    unit: Unit,
+) -> Result<Imported> {
+    let raw = std::fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse(&raw, path, source, aliases, input_unit, unit)
+}
+
+/// Read the sets of the main lifts from the contents of a csv export, the `path` is only used for errors
+fn parse(
+    raw: &str,
+    path: &Path,
+    source: Option<Source>,
+    aliases: &Aliases,
+    input_unit: Unit,
+    unit: Unit,
+) -> Result<Imported> {
+    // some exports use semicolons in countries with a decimal comma
+    let first_line = raw.lines().next().unwrap_or_default();
+    let delimiter = if first_line.contains(';') && !first_line.contains(',') {
+        b';'
+    } else {
+        b','
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(raw.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| parse_error(path, e.position().map(|p| p.line()), e.to_string()))?
+        .clone();
+    let source = source.unwrap_or_else(|| Source::detect(&headers));
+    let find = |names: &[&str]| {
+        headers
+            .iter()
+            .position(|h| names.iter().any(|name| h.eq_ignore_ascii_case(name)))
+    };
+    let missing = |name: &str| {
+        parse_error(
+            path,
+            Some(1),
+            format!("No {} column was found for a {:?} export", name, source),
+        )
+    };
+    let date = find(source.date_columns()).ok_or_else(|| missing("date"))?;
+    let exercise = find(source.exercise_columns()).ok_or_else(|| missing("exercise"))?;
+    let reps = find(&["reps"]).ok_or_else(|| missing("reps"))?;
+    let weight = headers
+        .iter()
+        .position(|h| h.to_lowercase().starts_with("weight"))
+        .ok_or_else(|| missing("weight"))?;
+    let warm_up = find(source.warm_up_columns());
+    let unit_column = find(&["unit"]).filter(|_| source == Source::Csv);
+    let header_unit = Unit::of_header(&headers[weight]);
+
+    let mut imported = Imported::default();
+    let mut days: BTreeMap<(NaiveDate, Lift), Vec<LoggedSet>> = BTreeMap::new();
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| parse_error(path, e.position().map(|p| p.line()), e.to_string()))?;
+        let line = record.position().map(|p| p.line());
+        let field = |idx: usize| record.get(idx).unwrap_or_default();
+        let name = field(exercise);
+        let lift = match aliases.lift(name) {
+            Some(lift) => lift,
+            None => {
+                if !name.is_empty() {
+                    *imported.unknown.entry(name.to_string()).or_default() += 1;
+                }
+                continue;
+            }
+        };
+        // sets without both a weight and reps, like timed or cardio sets, aren't imported
+        let (weight, reps) = match (parse_number(field(weight)), parse_number(field(reps))) {
+            (Some(weight), Some(reps)) if reps >= 1.0 => (weight, reps),
+            _ => continue,
+        };
+        let from = match unit_column.map(field).filter(|value| !value.is_empty()) {
+            Some(value) => value.parse().map_err(|e| parse_error(path, line, e))?,
+            None => header_unit.unwrap_or(input_unit),
+        };
+        let day = parse_date(field(date))
+            .ok_or_else(|| parse_error(path, line, format!("Invalid date {:?}", field(date))))?;
+        days.entry((day, lift)).or_default().push(LoggedSet {
+            weight: from.convert(weight.into(), unit),
+            reps: reps.round().min(u8::MAX as f32) as u8,
+            planned: None,
+            amrap: false,
+            warm_up: warm_up.is_some_and(|idx| source.is_warm_up(field(idx))),
+        });
+    }
+    imported.sessions = days
+        .into_iter()
+        .map(|((date, lift), sets)| LoggedSession {
+            date,
+            lift,
+            cycle: None,
+            week: None,
+            training_max: None,
+            sets,
+            supplemental: Vec::new(),
+        })
+        .collect();
+    Ok(imported)
+}
+
+/// A number that may use a decimal comma
+fn parse_number(text: &str) -> Option<f32> {
+    text.replace(',', ".")
+        .parse()
+        .ok()
+        .filter(|value: &f32| value.is_finite())
+}
+
+/// The day of a date or date and time, in the formats the apps export
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    const DATE_TIMES: &[&str] = &[
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%d %b %Y, %H:%M",
+        "%b %d, %Y, %I:%M %p",
+        "%m/%d/%Y %H:%M",
+    ];
+    const DATES: &[&str] = &["%Y-%m-%d", "%d %b %Y", "%m/%d/%Y", "%Y/%m/%d"];
+    DATE_TIMES
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|date_time| date_time.date())
+        .or_else(|| {
+            DATES
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        })
+}
+
+fn parse_error(path: &Path, line: Option<u64>, message: impl Into<String>) -> Error {
+    Error::Parse {
+        path: PathBuf::from(path),
+        line: line.map(|line| line as usize),
+        column: None,
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRONG: &str = "\
+Date,Workout Name,Duration,Exercise Name,Set Order,Weight,Reps,Distance,Seconds,Notes,Workout Notes,RPE
+2024-03-04 18:02:11,Evening,1h 2m,Bench Press (Barbell),W,95,10,0,0,,,
+2024-03-04 18:02:11,Evening,1h 2m,Bench Press (Barbell),1,185,5,0,0,,,
+2024-03-04 18:02:11,Evening,1h 2m,Bench Press (Barbell),2,185,4,0,0,,,
+2024-03-04 18:02:11,Evening,1h 2m,Plank,1,0,0,0,60,,,
+2024-03-04 18:02:11,Evening,1h 2m,Bicep Curl (Dumbbell),1,30,12,0,0,,,
+2024-03-06 18:10:00,Evening,58m,Squat (Barbell),1,275,3,0,0,,,
+";
+
+    const HEVY: &str = "\
+\"title\",\"start_time\",\"end_time\",\"description\",\"exercise_title\",\"superset_id\",\"exercise_notes\",\"set_index\",\"set_type\",\"weight_kg\",\"reps\",\"distance_km\",\"duration_seconds\",\"rpe\"
+\"Legs\",\"4 Mar 2024, 18:02\",\"4 Mar 2024, 19:04\",\"\",\"Squat (Barbell)\",,\"\",0,\"warmup\",60,5,,,
+\"Legs\",\"4 Mar 2024, 18:02\",\"4 Mar 2024, 19:04\",\"\",\"Squat (Barbell)\",,\"\",1,\"normal\",100,5,,,
+";
+
+    const FIT_NOTES: &str = "\
+Date;Exercise;Category;Weight (kgs);Reps;Distance;Distance Unit;Time
+2024-03-04;Flat Barbell Bench Press;Chest;82,5;5;;;
+2024-03-04;Flat Barbell Bench Press;Chest;85;3;;;
+2024-03-05;Deadlift;Back;140,25;1;;;
+";
+
+    const SPREADSHEET: &str = "\
+date,lift,weight,unit,reps,warm up
+03/04/2024,OHP,45,lbs,10,yes
+03/04/2024,OHP,100,lb,5,
+2024/03/05,deadlift,180,kg,3,no
+";
+
+    fn import(raw: &str, input_unit: Unit, unit: Unit) -> Imported {
+        parse(
+            raw,
+            Path::new("export.csv"),
+            None,
+            &Aliases::default(),
+            input_unit,
+            unit,
+        )
+        .unwrap()
+    }
+
+    fn headers(raw: &str) -> csv::StringRecord {
+        let delimiter = if raw.starts_with("Date;") { b';' } else { b',' };
+        csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(raw.as_bytes())
+            .headers()
+            .unwrap()
+            .clone()
+    }
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    /// The weight, reps and if it was a warm up for each set of a session
+    fn sets(session: &LoggedSession) -> Vec<(f32, u8, bool)> {
+        session
+            .sets
+            .iter()
+            .map(|set| (set.weight.into(), set.reps, set.warm_up))
+            .collect()
+    }
+
+    #[test]
+    fn detects_each_app_from_its_headers() {
+        assert_eq!(Source::detect(&headers(STRONG)), Source::Strong);
+        assert_eq!(Source::detect(&headers(HEVY)), Source::Hevy);
+        assert_eq!(Source::detect(&headers(FIT_NOTES)), Source::FitNotes);
+        assert_eq!(Source::detect(&headers(SPREADSHEET)), Source::Csv);
+    }
+
+    #[test]
+    fn reads_a_strong_export() {
+        let imported = import(STRONG, Unit::Lb, Unit::Lb);
+        assert_eq!(imported.sessions.len(), 2);
+        let bench = &imported.sessions[0];
+        assert_eq!((bench.date, bench.lift), (date("2024-03-04"), Lift::Bench));
+        assert_eq!(
+            sets(bench),
+            vec![(95.0, 10, true), (185.0, 5, false), (185.0, 4, false)]
+        );
+        assert_eq!(bench.cycle, None);
+        let squat = &imported.sessions[1];
+        assert_eq!((squat.date, squat.lift), (date("2024-03-06"), Lift::Squat));
+        assert_eq!(sets(squat), vec![(275.0, 3, false)]);
+        let unknown: Vec<_> = imported.unknown.into_iter().collect();
+        assert_eq!(
+            unknown,
+            vec![
+                ("Bicep Curl (Dumbbell)".to_string(), 1),
+                ("Plank".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_a_hevy_export_in_kg_from_its_header() {
+        // the weight_kg column overrides the unit the weights were expected in
+        let imported = import(HEVY, Unit::Lb, Unit::Lb);
+        assert_eq!(imported.sessions.len(), 1);
+        let squat = &imported.sessions[0];
+        assert_eq!((squat.date, squat.lift), (date("2024-03-04"), Lift::Squat));
+        assert_eq!(sets(squat), vec![(132.28, 5, true), (220.46, 5, false)]);
+    }
+
+    #[test]
+    fn reads_a_fit_notes_export_with_semicolons_and_decimal_commas() {
+        let imported = import(FIT_NOTES, Unit::Lb, Unit::Kg);
+        assert_eq!(imported.sessions.len(), 2);
+        let bench = &imported.sessions[0];
+        assert_eq!((bench.date, bench.lift), (date("2024-03-04"), Lift::Bench));
+        assert_eq!(sets(bench), vec![(82.5, 5, false), (85.0, 3, false)]);
+        let dead = &imported.sessions[1];
+        assert_eq!((dead.date, dead.lift), (date("2024-03-05"), Lift::Dead));
+        assert_eq!(sets(dead), vec![(140.25, 1, false)]);
+    }
+
+    #[test]
+    fn reads_a_spreadsheet_with_a_unit_column() {
+        let imported = import(SPREADSHEET, Unit::Kg, Unit::Kg);
+        assert_eq!(imported.sessions.len(), 2);
+        let ohp = &imported.sessions[0];
+        assert_eq!((ohp.date, ohp.lift), (date("2024-03-04"), Lift::Ohp));
+        assert_eq!(sets(ohp), vec![(20.41, 10, true), (45.36, 5, false)]);
+        let dead = &imported.sessions[1];
+        assert_eq!((dead.date, dead.lift), (date("2024-03-05"), Lift::Dead));
+        assert_eq!(sets(dead), vec![(180.0, 3, false)]);
+    }
+
+    #[test]
+    fn the_source_can_be_provided() {
+        // without a set order column a Strong export can't be detected
+        let raw = "Date,Exercise Name,Weight,Reps\n2024-03-04,Deadlift (Barbell),405,1\n";
+        assert_eq!(Source::detect(&headers(raw)), Source::Csv);
+        assert!(parse(
+            raw,
+            Path::new("export.csv"),
+            None,
+            &Aliases::default(),
+            Unit::Lb,
+            Unit::Lb
+        )
+        .is_err());
+        let imported = parse(
+            raw,
+            Path::new("export.csv"),
+            Some(Source::Strong),
+            &Aliases::default(),
+            Unit::Lb,
+            Unit::Lb,
+        )
+        .unwrap();
+        assert_eq!(sets(&imported.sessions[0]), vec![(405.0, 1, false)]);
+    }
+
+    #[test]
+    fn parses_each_date_format() {
+        let dates = [
+            "2024-03-04 18:02:11",
+            "2024-03-04 18:02",
+            "2024-03-04T18:02:11",
+            "4 Mar 2024, 18:02",
+            "Mar 04, 2024, 06:02 PM",
+            "03/04/2024 18:02",
+            "2024-03-04",
+            "4 Mar 2024",
+            "03/04/2024",
+            "2024/03/04",
+        ];
+        for text in &dates {
+            assert_eq!(parse_date(text), Some(date("2024-03-04")), "{}", text);
+        }
+        assert_eq!(parse_date("yesterday"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+    }
+
+    #[test]
+    fn parses_numbers_with_a_decimal_comma() {
+        assert_eq!(parse_number("82,5"), Some(82.5));
+        assert_eq!(parse_number("82.5"), Some(82.5));
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("inf"), None);
+    }
+
+    #[test]
+    fn converts_between_units() {
+        let convert =
+            |weight: f32, from: Unit, to: Unit| f32::from(from.convert(weight.into(), to));
+        assert_eq!(convert(100.0, Unit::Kg, Unit::Lb), 220.46);
+        assert_eq!(convert(225.0, Unit::Lb, Unit::Kg), 102.06);
+        assert_eq!(convert(82.5, Unit::Kg, Unit::Kg), 82.5);
+        assert_eq!(Unit::of_header("weight_kg"), Some(Unit::Kg));
+        assert_eq!(Unit::of_header("Weight (lbs)"), Some(Unit::Lb));
+        assert_eq!(Unit::of_header("Weight"), None);
+        assert_eq!("KGS".parse(), Ok(Unit::Kg));
+        assert!("stone".parse::<Unit>().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file,
+    plates::{PlateInventory, WeightsMap},
+    weight::Weight,
+};
+
+/// Everything you own for loading a barbell, as it is stored in an inventory file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Inventory {
+    /// Every bar you own, the first is used unless one is chosen by name
+    pub bars: Vec<Bar>,
+    /// Every plate you own
+    #[serde(default)]
+    pub plates: Vec<OwnedPlates>,
+    /// The weight of each collar, if you use them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collar: Option<Weight>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bar {
+    pub name: String,
+    pub weight: Weight,
+}
+
+/// How many of a plate you own, half of them can be loaded on each side of the bar
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OwnedPlates {
+    pub weight: Weight,
+    pub count: u8,
+}
+
+impl Default for Inventory {
+    /// A 45 lb bar with 8x45 2x35 2x25 4x10 2x5 2x2.5 plates
+    fn default() -> Self {
+        Self::from_weights_map(&WeightsMap::default())
+    }
+}
+
+impl Inventory {
+    /// Read an inventory from a .toml, .json or .yaml file
+    pub fn from_path(path: &Path) -> Result<Self> {
+        file::read(path)
+    }
+
+    /// The bar named `name`, or the first bar when `name` is `None`
+    pub fn bar(&self, name: Option<&str>) -> Result<&Bar> {
+        match name {
+            Some(name) => self
+                .bars
+                .iter()
+                .find(|bar| bar.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    let names: Vec<&str> = self.bars.iter().map(|bar| bar.name.as_str()).collect();
+                    Error::Validation(vec![format!(
+                        "Unknown bar {:?}, expected one of {:?}",
+                        name, names
+                    )])
+                }),
+            None => self.bars.first().ok_or_else(|| {
+                Error::Validation(vec!["The inventory doesn't include any bars".to_string()])
+            }),
+        }
+    }
+
+    /// The plates that can be loaded on `bar`, with the collars included in its weight.
+    /// More than `MAX_PER_SIDE` of a plate on each side of the bar are left out
+    pub fn plate_inventory(&self, bar: &Bar) -> PlateInventory {
+        let per_side: HashMap<Weight, u8> = self
+            .plate_counts()
+            .into_iter()
+            .map(|(weight, count)| (weight, u8::try_from(count / 2).unwrap_or(MAX_PER_SIDE)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        PlateInventory::new(&per_side)
+            .with_bar(bar.weight)
+            .with_collar(self.collar.unwrap_or_default())
+    }
+
+    /// How many of each plate you own, adding together a plate listed more than once
+    pub fn plate_counts(&self) -> BTreeMap<Weight, u32> {
+        let mut counts = BTreeMap::new();
+        for plates in &self.plates {
+            *counts.entry(plates.weight).or_insert(0) += u32::from(plates.count);
+        }
+        counts
+    }
+
+    /// Plates that can't be split evenly between the sides of the bar
+    pub fn unpaired_plates(&self) -> Vec<Weight> {
+        self.plate_counts()
+            .into_iter()
+            .filter(|(_, count)| count % 2 != 0)
+            .map(|(weight, _)| weight)
+            .collect()
+    }
+
+    /// The inventory needed to reach every weight in a weights map, the
+    /// bar is the weight that doesn't need any plates so includes any collars
+    pub fn from_weights_map(weights: &WeightsMap) -> Self {
+        let inventory = PlateInventory::from_weights_map(weights);
+        Self {
+            bars: vec![Bar {
+                name: DEFAULT_BAR_NAME.to_string(),
+                weight: inventory.bar(),
+            }],
+            plates: inventory
+                .plates()
+                .iter()
+                .map(|(weight, count)| OwnedPlates {
+                    weight: *weight,
+                    count: count.saturating_mul(2),
+                })
+                .collect(),
+            collar: None,
+        }
+    }
+}
+
+const DEFAULT_BAR_NAME: &str = "barbell";
+/// The most of one plate that can be loaded on each side of the bar
+pub const MAX_PER_SIDE: u8 = u8::MAX;
@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// The paper size a plan will be printed on
+#[derive(Debug, Clone, Copy)]
+pub enum PageSize {
+    Letter,
+    Legal,
+    A4,
+    A5,
+    /// The width and height of the page in inches
+    Custom(f64, f64),
+}
+
+impl PageSize {
+    /// The width and height of this page in inches, portrait orientation
+    fn dimensions(self) -> (f64, f64) {
+        match self {
+            Self::Letter => (8.5, 11.0),
+            Self::Legal => (8.5, 14.0),
+            Self::A4 => (210.0 / MM_PER_INCH, 297.0 / MM_PER_INCH),
+            Self::A5 => (148.0 / MM_PER_INCH, 210.0 / MM_PER_INCH),
+            Self::Custom(width, height) => (width.min(height), width.max(height)),
+        }
+    }
+}
+
+const MM_PER_INCH: f64 = 25.4;
+
+impl FromStr for PageSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        let ret = match lower.as_str() {
+            "letter" => Self::Letter,
+            "legal" => Self::Legal,
+            "a4" => Self::A4,
+            "a5" => Self::A5,
+            _ => {
+                let (dims, scale) = if let Some(dims) = lower.strip_suffix("mm") {
+                    (dims, 1.0 / MM_PER_INCH)
+                } else {
+                    (lower.trim_end_matches("in"), 1.0)
+                };
+                let mut parts = dims.split('x');
+                let mut next_dim = || -> Result<f64, String> {
+                    parts
+                        .next()
+                        .and_then(|part| part.trim().parse::<f64>().ok())
+                        .filter(|dim| *dim > 0.0)
+                        .map(|dim| dim * scale)
+                        .ok_or_else(|| format!("Unknown page size: {:?}", s))
+                };
+                let width = next_dim()?;
+                let height = next_dim()?;
+                Self::Custom(width, height)
+            }
+        };
+        Ok(ret)
+    }
+}
+
+const PAGE_MARGIN_TOP: f64 = 0.25;
+const PAGE_MARGIN_SIDE: f64 = 0.5;
+const PAGE_MARGIN_BOTTOM: f64 = 0.5;
+/// The size of a day, including its margin, the template was originally designed around
+const BASE_DAY_WIDTH: f64 = 5.0;
+const BASE_DAY_HEIGHT: f64 = 7.75;
+const BASE_DAY_MARGIN: f64 = 0.5;
+/// The space taken up by the headings and main lift sets of a day
+pub const DAY_HEADER_HEIGHT: f64 = 1.75;
+/// The extra space taken up by a row of warm up sets
+pub const WARM_UP_HEIGHT: f64 = 0.5;
+/// The extra space taken up by plate diagrams and changes under each set
+pub const DIAGRAM_ROW_HEIGHT: f64 = 0.6;
+/// The extra space taken up by a line of jumps and throws or conditioning
+pub const COMPONENT_HEIGHT: f64 = 0.3;
+const SUPPORT_ROW_HEIGHT: f64 = 0.38;
+
+/// The css dimensions (in inches) of the html plan, derived from
+/// the page size and the number of days per page
+#[derive(Debug, Serialize, Clone)]
+pub struct Layout {
+    page_width: f64,
+    page_height: f64,
+    margin_top: f64,
+    margin_side: f64,
+    margin_bottom: f64,
+    day_width: f64,
+    day_height: f64,
+    day_margin: f64,
+    font_size: f64,
+    side_font_size: f64,
+    support_rows: usize,
+}
+
+impl Layout {
+    /// `header_height` is the space needed for everything above the
+    /// supporting work for a day when printed at full size
+    pub fn new(page_size: PageSize, days_per_page: u8, header_height: f64) -> Self {
+        let days = days_per_page.max(1) as usize;
+        let (short, long) = page_size.dimensions();
+        // try both orientations and every way to split the page into a grid,
+        // keeping whichever gives each day the most room
+        let mut best = (0.0, short, long, short, long);
+        for &(page_width, page_height) in &[(long, short), (short, long)] {
+            let usable_width = page_width - PAGE_MARGIN_SIDE * 2.0;
+            let usable_height = page_height - PAGE_MARGIN_TOP - PAGE_MARGIN_BOTTOM;
+            for columns in 1..=days {
+                let rows = days.div_ceil(columns);
+                let cell_width = usable_width / columns as f64;
+                let cell_height = usable_height / rows as f64;
+                let scale = (cell_width / BASE_DAY_WIDTH).min(cell_height / BASE_DAY_HEIGHT);
+                if scale > best.0 {
+                    best = (scale, page_width, page_height, cell_width, cell_height);
+                }
+            }
+        }
+        let (scale, page_width, page_height, cell_width, cell_height) = best;
+        let day_margin = BASE_DAY_MARGIN * scale.min(1.0);
+        let day_width = cell_width - day_margin * 2.0;
+        let day_height = cell_height - day_margin * 2.0;
+        let text_scale = (day_width / (BASE_DAY_WIDTH - BASE_DAY_MARGIN * 2.0)).min(1.5);
+        let support_rows = ((day_height - header_height * text_scale)
+            / (SUPPORT_ROW_HEIGHT * text_scale))
+            .floor()
+            .max(0.0) as usize;
+        Self {
+            page_width: round_dimension(page_width),
+            page_height: round_dimension(page_height),
+            margin_top: PAGE_MARGIN_TOP,
+            margin_side: PAGE_MARGIN_SIDE,
+            margin_bottom: PAGE_MARGIN_BOTTOM,
+            day_width: round_dimension(day_width),
+            day_height: round_dimension(day_height),
+            day_margin: round_dimension(day_margin),
+            font_size: round_dimension(11.0 * text_scale),
+            side_font_size: round_dimension(8.0 * text_scale),
+            support_rows,
+        }
+    }
+}
+
+fn round_dimension(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
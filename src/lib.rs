@@ -0,0 +1,52 @@
+//! Generate 5/3/1 weightlifting plans
+//!
+//! A `Program` is built from the training max for each lift and is made up of
+//! `Week`s, each with a `Day` per lift containing the warm up and work `Set`s.
+//! The plates for each set are chosen by a `LoadingPlanner` from a `PlateInventory`
+//! and a program can be rendered as plain text, a printable html page or browsed
+//! interactively with a `Tui`. A `Profile` schedules a `Session` for each training
+//! day, with the results kept in a training `Log`.
+//!
+//! ```
+//! use five_three_one::{
+//!     loading::LoadingPlanner,
+//!     plan::{Program, TrainingMax},
+//!     plates::{default_weights, PlateInventory},
+//!     render::RenderOptions,
+//!     supports::Supports,
+//!     terminal,
+//! };
+//!
+//! let program = Program::builder(TrainingMax::from_one_rep_maxes(215.0, 200.0, 125.0, 95.0))
+//!     .cycles(3)
+//!     .build();
+//! let planner = LoadingPlanner::new(PlateInventory::from_weights_map(&default_weights()));
+//! let text = terminal::render(&program, &Supports::default(), &planner, RenderOptions::default());
+//! println!("{}", text);
+//! ```
+
+pub mod chart;
+pub mod diagram;
+pub mod error;
+pub mod file;
+pub mod implements;
+pub mod import;
+pub mod inventory;
+pub mod layout;
+pub mod loading;
+pub mod log;
+pub mod plan;
+pub mod plates;
+pub mod profile;
+pub mod records;
+pub mod render;
+pub mod report;
+pub mod session;
+pub mod stats;
+pub mod strength;
+pub mod suggest;
+pub mod supports;
+pub mod terminal;
+pub mod tui;
+pub mod validate;
+pub mod weight;
@@ -0,0 +1,428 @@
+use std::{collections::BTreeMap, iter};
+
+use indexmap::IndexMap;
+
+use crate::{plates::PlateInventory, weight::Weight};
+
+/// A single change to the plates on one side of the bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlateStep {
+    /// Put this plate on the outside of the bar
+    Add(Weight),
+    /// Take this plate off the outside of the bar
+    Remove(Weight),
+}
+
+impl std::fmt::Display for PlateStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Add(plate) => write!(f, "add {}", plate),
+            Self::Remove(plate) => write!(f, "remove {}", plate),
+        }
+    }
+}
+
+/// The plates on one side of the bar for a set, from the collar
+/// outward, and the steps to get there from the previous set
+#[derive(Debug, Clone, PartialEq)]
+pub struct SideLoading {
+    /// The plates on one side of the bar, from the collar outward
+    pub plates: Vec<Weight>,
+    /// The steps to get from the previous set's plates to these
+    pub steps: Vec<PlateStep>,
+}
+
+/// The plates on both sides of the bar for a set
+#[derive(Debug, Clone)]
+pub struct Loading {
+    pub left: SideLoading,
+    pub right: SideLoading,
+}
+
+impl Loading {
+    /// If both sides have the same plates
+    pub fn is_even(&self) -> bool {
+        self.left.plates == self.right.plates
+    }
+}
+
+/// The plates on the left and right of the bar
+type Sides = (Vec<Weight>, Vec<Weight>);
+
+/// The most loadings considered for a single weight
+const MAX_LOADINGS: usize = 32;
+/// The most partial plans kept between sets
+const MAX_PLANS: usize = 256;
+
+/// Chooses the plates for a sequence of sets so that the
+/// number of plates moved over the whole sequence is as small as possible
+pub struct LoadingPlanner {
+    inventory: PlateInventory,
+    max_imbalance: Weight,
+}
+
+impl LoadingPlanner {
+    pub fn new(inventory: PlateInventory) -> Self {
+        Self {
+            inventory,
+            max_imbalance: Weight::ZERO,
+        }
+    }
+
+    /// Allow one side of the bar to be heavier than the other by up to `max_imbalance`
+    /// when a weight can't be reached by loading both sides the same, defaults to 0
+    pub fn with_max_imbalance(mut self, max_imbalance: Weight) -> Self {
+        self.max_imbalance = max_imbalance;
+        self
+    }
+
+    pub fn inventory(&self) -> &PlateInventory {
+        &self.inventory
+    }
+
+    /// The smallest step between two weights these plates can make, zero without any plates
+    pub fn resolution(&self) -> Weight {
+        let plates = || {
+            self.inventory
+                .plates()
+                .iter()
+                .map(|(plate, _)| plate.thousandths())
+                .filter(|plate| *plate > 0)
+        };
+        let step = plates().fold(0, gcd);
+        let smallest = plates().min().unwrap_or_default();
+        if step > 0 && self.max_imbalance.thousandths() < smallest {
+            // both sides get the same plates
+            Weight::from_thousandths(step * 2)
+        } else {
+            Weight::from_thousandths(step)
+        }
+    }
+
+    /// If there is any way to reach the provided total weight
+    pub fn can_load(&self, weight: Weight) -> bool {
+        !self.loadings_for(weight).is_empty()
+    }
+
+    /// Every combination of plates that will reach the provided total weight,
+    /// fewest plates first. Both sides are loaded the same when possible,
+    /// otherwise they can differ by up to the maximum imbalance
+    fn loadings_for(&self, weight: Weight) -> Vec<Sides> {
+        let plates = (weight - self.inventory.empty()).thousandths();
+        if plates < 0 {
+            return Vec::new();
+        }
+        let mut ret = Vec::new();
+        if plates % 2 == 0 {
+            let half = plates / 2;
+            let found = self.sides_between(half, half).remove(&half);
+            ret = found
+                .unwrap_or_default()
+                .into_iter()
+                .map(|side| (side.clone(), side))
+                .collect();
+        }
+        if ret.is_empty() && self.max_imbalance.is_positive() {
+            ret = self.uneven_loadings_for(plates);
+        }
+        ret.sort_by_key(|(left, right)| left.len() + right.len());
+        ret.truncate(MAX_LOADINGS);
+        ret
+    }
+
+    /// Every pair of sides that add up to `plates`, with the heavier side on the
+    /// left and no more than the maximum imbalance between them, fewest plates first
+    fn uneven_loadings_for(&self, plates: i64) -> Vec<Sides> {
+        // each side has to be within half the imbalance of half the plates
+        let imbalance = self.max_imbalance.thousandths();
+        let lightest = (plates - imbalance + 1).div_euclid(2);
+        let heaviest = (plates + imbalance).div_euclid(2);
+        let by_weight = self.sides_between(lightest, heaviest);
+        let mut ret = Vec::new();
+        for (left_weight, lefts) in &by_weight {
+            let right_weight = plates - left_weight;
+            let imbalance = left_weight - right_weight;
+            if imbalance <= 0 || imbalance > self.max_imbalance.thousandths() {
+                continue;
+            }
+            if let Some(rights) = by_weight.get(&right_weight) {
+                for left in lefts {
+                    for right in rights {
+                        ret.push((left.clone(), right.clone()));
+                    }
+                }
+            }
+        }
+        ret.sort_by_key(|(left, right)| left.len() + right.len());
+        ret.truncate(MAX_LOADINGS);
+        ret
+    }
+
+    /// The `MAX_LOADINGS` combinations of plates with the fewest plates for each weight from
+    /// `lightest` to `heaviest` a side can be loaded to. Built up one plate at a time, so the
+    /// combinations kept for a weight are only ever extended by the plates after them
+    fn sides_between(&self, lightest: i64, heaviest: i64) -> BTreeMap<i64, Vec<Vec<Weight>>> {
+        let plates: Vec<(Weight, u8)> = self
+            .inventory
+            .plates()
+            .iter()
+            .copied()
+            // invalid plates are reported by validation, just skip them here
+            .filter(|(plate, _)| plate.is_positive())
+            .collect();
+        let mut by_weight: BTreeMap<i64, Vec<Vec<Weight>>> = BTreeMap::new();
+        by_weight.insert(0, vec![Vec::new()]);
+        for (idx, &(plate, count)) in plates.iter().enumerate() {
+            // drop the sides that every plate left couldn't make heavy enough
+            let reach: i64 = plates[idx..]
+                .iter()
+                .map(|(plate, count)| plate.thousandths() * *count as i64)
+                .sum();
+            by_weight.retain(|weight, _| weight + reach >= lightest);
+            let size = plate.thousandths();
+            let mut next = by_weight.clone();
+            for (weight, sides) in &by_weight {
+                let most = ((heaviest - weight) / size).min(count as i64);
+                for used in 1..=most {
+                    let kept = next.entry(weight + size * used).or_default();
+                    for side in sides {
+                        let mut side = side.clone();
+                        side.extend(iter::repeat_n(plate, used as usize));
+                        kept.push(side);
+                    }
+                    // fewest plates first, then the most of the heavier plates
+                    kept.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| b.cmp(a)));
+                    kept.truncate(MAX_LOADINGS);
+                }
+            }
+            by_weight = next;
+        }
+        by_weight.retain(|weight, _| *weight >= lightest);
+        by_weight
+    }
+
+    /// Plan the plates for each of the sets in order, starting from an empty bar.
+    /// Any set that can't be reached with the available plates will be `None`
+    pub fn plan(&self, sets: &[Weight]) -> Vec<Option<Loading>> {
+        let mut plans = vec![PartialPlan::default()];
+        for set in sets {
+            let loadings = self.loadings_for(*set);
+            if loadings.is_empty() {
+                for plan in &mut plans {
+                    plan.history.push(None);
+                }
+                continue;
+            }
+            // an index map keeps the order plans are found in, so ties are always broken the same way
+            let mut next: IndexMap<Sides, PartialPlan> = IndexMap::new();
+            for plan in &plans {
+                for (left, right) in &loadings {
+                    let (left, left_moved) = restack(&plan.bar.0, left);
+                    let (right, right_moved) = restack(&plan.bar.1, right);
+                    let moved = plan.moved + left_moved + right_moved;
+                    let bar = (left, right);
+                    if let Some(existing) = next.get(&bar) {
+                        if existing.moved <= moved {
+                            continue;
+                        }
+                    }
+                    let mut history = plan.history.clone();
+                    history.push(Some(bar.clone()));
+                    next.insert(
+                        bar.clone(),
+                        PartialPlan {
+                            bar,
+                            moved,
+                            history,
+                        },
+                    );
+                }
+            }
+            plans = next.into_iter().map(|(_, plan)| plan).collect();
+            plans.sort_by(|l, r| {
+                l.moved
+                    .cmp(&r.moved)
+                    .then((l.bar.0.len() + l.bar.1.len()).cmp(&(r.bar.0.len() + r.bar.1.len())))
+            });
+            plans.truncate(MAX_PLANS);
+        }
+        let history = plans
+            .into_iter()
+            .next()
+            .map(|plan| plan.history)
+            .unwrap_or_default();
+        let mut bar: Sides = Default::default();
+        history
+            .into_iter()
+            .map(|sides| {
+                let sides = sides?;
+                let loading = Loading {
+                    left: side_loading(&bar.0, &sides.0),
+                    right: side_loading(&bar.1, &sides.1),
+                };
+                bar = sides;
+                Some(loading)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct PartialPlan {
+    /// The plates currently on the bar
+    bar: Sides,
+    /// How many plates have been moved so far
+    moved: usize,
+    /// The plates on the bar after each set
+    history: Vec<Option<Sides>>,
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn side_loading(from: &[Weight], to: &[Weight]) -> SideLoading {
+    SideLoading {
+        plates: to.to_vec(),
+        steps: steps_between(from, to),
+    }
+}
+
+/// Put the plates for the next set on the bar by keeping as many of the
+/// inner plates as possible, returning the new plates and how many were moved.
+/// A plate is never put outside of a lighter plate
+fn restack(bar: &[Weight], loading: &[Weight]) -> (Vec<Weight>, usize) {
+    let mut needed = loading.to_vec();
+    let mut kept = 0;
+    for plate in bar {
+        if let Some(idx) = needed.iter().position(|p| p == plate) {
+            needed.remove(idx);
+            kept += 1;
+        } else {
+            break;
+        }
+    }
+    while kept > 0 && needed.iter().any(|p| *p > bar[kept - 1]) {
+        kept -= 1;
+        needed.push(bar[kept]);
+    }
+    needed.sort_by_key(|p| std::cmp::Reverse(*p));
+    let mut plates = bar[..kept].to_vec();
+    plates.extend(needed.iter().copied());
+    (plates, (bar.len() - kept) + needed.len())
+}
+
+/// The plates to take off (outermost first) and then put on
+/// to get from one set of plates to another
+fn steps_between(from: &[Weight], to: &[Weight]) -> Vec<PlateStep> {
+    let kept = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(l, r)| l == r)
+        .count();
+    from[kept..]
+        .iter()
+        .rev()
+        .map(|p| PlateStep::Remove(*p))
+        .chain(to[kept..].iter().map(|p| PlateStep::Add(*p)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn lb(weight: f32) -> Weight {
+        Weight::from(weight)
+    }
+
+    fn planner() -> LoadingPlanner {
+        let plates: HashMap<Weight, u8> = [
+            (45.0, 4),
+            (35.0, 1),
+            (25.0, 1),
+            (10.0, 2),
+            (5.0, 1),
+            (2.5, 1),
+        ]
+        .iter()
+        .map(|(plate, count)| (lb(*plate), *count))
+        .collect();
+        LoadingPlanner::new(PlateInventory::new(&plates))
+    }
+
+    #[test]
+    fn restack_takes_off_lighter_plates_before_a_heavier_one() {
+        let (plates, moved) = restack(&[lb(10.0), lb(2.5)], &[lb(10.0), lb(5.0), lb(2.5)]);
+        assert_eq!(plates, vec![lb(10.0), lb(5.0), lb(2.5)]);
+        assert_eq!(moved, 3);
+    }
+
+    #[test]
+    fn restack_keeps_inner_plates() {
+        let (plates, moved) = restack(&[lb(45.0), lb(10.0)], &[lb(45.0), lb(10.0), lb(5.0)]);
+        assert_eq!(plates, vec![lb(45.0), lb(10.0), lb(5.0)]);
+        assert_eq!(moved, 1);
+    }
+
+    #[test]
+    fn plates_are_never_outside_a_lighter_plate() {
+        let planner = planner();
+        // a day's warm up and work sets followed by lighter supplemental sets
+        let sets: Vec<Weight> = [
+            95.0, 120.0, 145.0, 185.0, 210.0, 240.0, 160.0, 175.0, 135.0, 285.0, 100.0, 315.0,
+        ]
+        .iter()
+        .map(|weight| lb(*weight))
+        .collect();
+        let mut bar = Vec::new();
+        for (weight, loading) in sets.iter().zip(planner.plan(&sets)) {
+            let loading = loading.unwrap_or_else(|| panic!("{} can't be loaded", weight));
+            let side = loading.left.plates;
+            assert!(
+                side.windows(2).all(|pair| pair[0] >= pair[1]),
+                "{} is loaded with {:?}",
+                weight,
+                side
+            );
+            assert_eq!(
+                planner.inventory().empty() + side.iter().sum::<Weight>() * 2,
+                *weight
+            );
+            for step in loading.left.steps {
+                match step {
+                    PlateStep::Add(plate) => bar.push(plate),
+                    PlateStep::Remove(plate) => assert_eq!(bar.pop(), Some(plate)),
+                }
+            }
+            assert_eq!(bar, side, "the steps to {} don't reach its plates", weight);
+        }
+    }
+
+    #[test]
+    fn uneven_loadings_stay_within_the_imbalance() {
+        let plates: HashMap<Weight, u8> = [45.0, 25.0, 10.0, 5.0, 2.5, 1.25, 0.5, 0.25]
+            .iter()
+            .map(|plate| (lb(*plate), 16))
+            .collect();
+        let planner = LoadingPlanner::new(PlateInventory::new(&plates)).with_max_imbalance(lb(2.5));
+        let weight = lb(316.25);
+        let loadings = planner.loadings_for(weight);
+        assert!(!loadings.is_empty());
+        assert!(loadings.len() <= MAX_LOADINGS);
+        for (left, right) in &loadings {
+            let (left, right) = (left.iter().sum::<Weight>(), right.iter().sum::<Weight>());
+            assert_eq!(planner.inventory().empty() + left + right, weight);
+            assert!(left > right && left - right <= lb(2.5));
+        }
+        assert!(loadings
+            .windows(2)
+            .all(|pair| pair[0].0.len() + pair[0].1.len() <= pair[1].0.len() + pair[1].1.len()));
+    }
+}
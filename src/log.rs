@@ -0,0 +1,178 @@
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file::{self, Format},
+    plan::{one_rep_max, Lift},
+    weight::Weight,
+};
+
+/// Every session that has been done, as it is stored in a training log file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Log {
+    #[serde(default)]
+    pub sessions: Vec<LoggedSession>,
+    /// The athlete's bodyweight over time, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bodyweights: Vec<Weighing>,
+}
+
+/// A bodyweight recorded on a day
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Weighing {
+    #[serde(deserialize_with = "file::date")]
+    pub date: NaiveDate,
+    pub weight: Weight,
+}
+
+/// The sets done for one lift on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedSession {
+    #[serde(deserialize_with = "file::date")]
+    pub date: NaiveDate,
+    pub lift: Lift,
+    /// The cycle of the plan, starting at 1, when the session came from a plan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle: Option<u32>,
+    /// The week of the cycle, 1 through 4
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub training_max: Option<Weight>,
+    #[serde(default)]
+    pub sets: Vec<LoggedSet>,
+    /// The supporting exercises that were done
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supplemental: Vec<LoggedSupport>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LoggedSet {
+    pub weight: Weight,
+    /// The reps that were actually done
+    pub reps: u8,
+    /// The reps that were planned, the minimum for an amrap set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planned: Option<u8>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub amrap: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub warm_up: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedSupport {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<Weight>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl Log {
+    /// Read a training log from a .toml, .json or .yaml file, a log
+    /// that doesn't exist yet is empty
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match file::read(path) {
+            Err(Error::Read { source, .. }) if source.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Write the log to a .toml, .json or .yaml file, replacing what was there
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let text = Format::of(path)?.to_string(self)?;
+        file::write(path, text)
+    }
+
+    /// Add a session, keeping the sessions in date order
+    pub fn push(&mut self, session: LoggedSession) {
+        let idx = self
+            .sessions
+            .iter()
+            .rposition(|logged| logged.date <= session.date)
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+        self.sessions.insert(idx, session);
+    }
+
+    /// Record the bodyweight on `date`, replacing any already recorded that day
+    pub fn record_bodyweight(&mut self, date: NaiveDate, weight: Weight) {
+        self.bodyweights.retain(|weighing| weighing.date != date);
+        let idx = self
+            .bodyweights
+            .iter()
+            .rposition(|weighing| weighing.date < date)
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+        self.bodyweights.insert(idx, Weighing { date, weight });
+    }
+
+    /// The latest bodyweight recorded on or before `date`
+    pub fn bodyweight(&self, date: NaiveDate) -> Option<Weight> {
+        self.bodyweights
+            .iter()
+            .rev()
+            .find(|weighing| weighing.date <= date)
+            .map(|weighing| weighing.weight)
+    }
+
+    /// The training max of the most recent session of `lift` that recorded one
+    pub fn latest_training_max(&self, lift: Lift) -> Option<Weight> {
+        self.sessions
+            .iter()
+            .rev()
+            .filter(|session| session.lift == lift)
+            .find_map(|session| session.training_max)
+    }
+
+    /// If every amrap set logged for `lift` in `cycle` reached its planned
+    /// reps, `None` when there aren't any logged
+    pub fn amraps_met(&self, lift: Lift, cycle: u32) -> Option<bool> {
+        let mut amraps = self
+            .sessions
+            .iter()
+            .filter(|session| session.lift == lift && session.cycle == Some(cycle))
+            .flat_map(|session| session.sets.iter())
+            .filter(|set| set.amrap)
+            .peekable();
+        amraps.peek()?;
+        Some(amraps.all(|set| set.reps >= set.planned.unwrap_or_default()))
+    }
+
+    /// The training max for `lift` in `cycle`, it goes up by `increment` after each
+    /// cycle unless one of that cycle's amrap sets fell short of its planned reps
+    pub fn training_max(&self, lift: Lift, start: Weight, increment: Weight, cycle: u32) -> Weight {
+        (1..cycle)
+            .filter(|cycle| self.amraps_met(lift, *cycle) != Some(false))
+            .fold(start, |training_max, _| training_max + increment)
+    }
+
+    /// The sessions already logged for `lift` on `date`
+    pub fn on(&self, date: NaiveDate, lift: Lift) -> impl Iterator<Item = &LoggedSession> {
+        self.sessions
+            .iter()
+            .filter(move |session| session.date == date && session.lift == lift)
+    }
+
+    /// The work set for `lift` from `since` onward with the highest
+    /// estimated one rep max, along with the day it was done
+    pub fn best_set(&self, lift: Lift, since: NaiveDate) -> Option<(NaiveDate, LoggedSet)> {
+        self.sessions
+            .iter()
+            .filter(|session| session.lift == lift && session.date >= since)
+            .flat_map(|session| session.sets.iter().map(move |set| (session.date, *set)))
+            .filter(|(_, set)| !set.warm_up && set.reps > 0)
+            .max_by(|(_, a), (_, b)| {
+                one_rep_max(a.weight.into(), a.reps)
+                    .total_cmp(&one_rep_max(b.weight.into(), b.reps))
+            })
+    }
+}
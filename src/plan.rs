@@ -0,0 +1,586 @@
+use std::{convert::TryFrom, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{plates::BAR, records::Records, weight::Weight};
+
+/// One of the four main lifts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", try_from = "String", into = "String")]
+pub enum Lift {
+    Bench,
+    Squat,
+    Ohp,
+    Dead,
+}
+
+impl Lift {
+    /// Every lift, in the order they are trained each week
+    pub const ALL: [Lift; 4] = [Lift::Bench, Lift::Squat, Lift::Ohp, Lift::Dead];
+
+    /// The name used for this lift's day in a plan
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bench => "Bench",
+            Self::Squat => "Squats",
+            Self::Ohp => "OHP",
+            Self::Dead => "Deads",
+        }
+    }
+}
+
+impl FromStr for Lift {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "bench" => Self::Bench,
+            "squat" | "squats" => Self::Squat,
+            "ohp" => Self::Ohp,
+            "dead" | "deads" | "deadlift" => Self::Dead,
+            _ => return Err(format!("Unknown lift: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+impl TryFrom<String> for Lift {
+    type Error = String;
+
+    /// Parsed the same as `FromStr`, so lifts can also be used as map keys
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Lift> for String {
+    /// The lowercase name, the same one `FromStr` parses
+    fn from(lift: Lift) -> Self {
+        match lift {
+            Lift::Bench => "bench",
+            Lift::Squat => "squat",
+            Lift::Ohp => "ohp",
+            Lift::Dead => "dead",
+        }
+        .to_string()
+    }
+}
+
+/// A value for each of the lifts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerLift<T> {
+    pub squat: T,
+    pub dead: T,
+    pub bench: T,
+    pub ohp: T,
+}
+
+impl<T: Copy> PerLift<T> {
+    /// The same value for every lift
+    pub fn all(value: T) -> Self {
+        Self {
+            squat: value,
+            dead: value,
+            bench: value,
+            ohp: value,
+        }
+    }
+
+    pub fn get(&self, lift: Lift) -> T {
+        match lift {
+            Lift::Squat => self.squat,
+            Lift::Dead => self.dead,
+            Lift::Bench => self.bench,
+            Lift::Ohp => self.ohp,
+        }
+    }
+
+    pub fn set(&mut self, lift: Lift, value: T) {
+        match lift {
+            Lift::Squat => self.squat = value,
+            Lift::Dead => self.dead = value,
+            Lift::Bench => self.bench = value,
+            Lift::Ohp => self.ohp = value,
+        }
+    }
+}
+
+/// How much each training max goes up every cycle by default,
+/// +10 for squats and deads and +5 for bench and ohp
+pub const DEFAULT_INCREMENTS: PerLift<Weight> = PerLift {
+    squat: Weight::from_thousandths(10_000),
+    dead: Weight::from_thousandths(10_000),
+    bench: Weight::from_thousandths(5_000),
+    ohp: Weight::from_thousandths(5_000),
+};
+
+/// The multiple every weight is rounded up to by default
+pub const DEFAULT_ROUNDING: Weight = Weight::from_thousandths(5_000);
+
+/// The percent of a one rep max used as the training max by default
+pub const DEFAULT_TRAINING_MAX_PERCENT: f32 = 90.0;
+
+/// The training max for each lift, all of the sets in a plan
+/// are calculated as a percentage of these
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TrainingMax {
+    pub squat: Weight,
+    pub dead: Weight,
+    pub bench: Weight,
+    pub ohp: Weight,
+}
+
+impl TrainingMax {
+    pub fn new(squat: Weight, dead: Weight, bench: Weight, ohp: Weight) -> Self {
+        Self {
+            squat,
+            dead,
+            bench,
+            ohp,
+        }
+    }
+
+    /// Training maxes of `DEFAULT_TRAINING_MAX_PERCENT` of each of the one rep maxes provided,
+    /// rounded with `round_weight`
+    pub fn from_one_rep_maxes(squat: f32, dead: f32, bench: f32, ohp: f32) -> Self {
+        Self::from_percent_of(
+            &PerLift {
+                squat,
+                dead,
+                bench,
+                ohp,
+            },
+            &PerLift::all(DEFAULT_TRAINING_MAX_PERCENT),
+            &PerLift::all(DEFAULT_ROUNDING),
+        )
+    }
+
+    /// Training maxes of `percent` of each lift's one rep max, rounded
+    /// up to a multiple of the lift's `rounding` the same way as its weights
+    pub fn from_percent_of(
+        one_rep_maxes: &PerLift<f32>,
+        percent: &PerLift<f32>,
+        rounding: &PerLift<Weight>,
+    ) -> Self {
+        let training_max = |lift: Lift| {
+            percent_of(
+                Weight::from(one_rep_maxes.get(lift)),
+                percent.get(lift) / 100.0,
+                rounding.get(lift),
+            )
+        };
+        Self::new(
+            training_max(Lift::Squat),
+            training_max(Lift::Dead),
+            training_max(Lift::Bench),
+            training_max(Lift::Ohp),
+        )
+    }
+
+    pub fn get(&self, lift: Lift) -> Weight {
+        match lift {
+            Lift::Squat => self.squat,
+            Lift::Dead => self.dead,
+            Lift::Bench => self.bench,
+            Lift::Ohp => self.ohp,
+        }
+    }
+
+    pub fn set(&mut self, lift: Lift, value: Weight) {
+        match lift {
+            Lift::Squat => self.squat = value,
+            Lift::Dead => self.dead = value,
+            Lift::Bench => self.bench = value,
+            Lift::Ohp => self.ohp = value,
+        }
+    }
+
+    /// The training maxes for the cycle after this one, using `DEFAULT_INCREMENTS`
+    pub fn next_cycle(&self) -> Self {
+        self.next_cycle_by(&DEFAULT_INCREMENTS)
+    }
+
+    /// The training maxes for the cycle after this one, each lift going up by its increment
+    pub fn next_cycle_by(&self, increments: &PerLift<Weight>) -> Self {
+        Self::new(
+            self.squat + increments.squat,
+            self.dead + increments.dead,
+            self.bench + increments.bench,
+            self.ohp + increments.ohp,
+        )
+    }
+}
+
+/// A single set of a lift
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Set {
+    /// The total weight on the bar
+    pub weight: Weight,
+    /// The prescribed reps, the minimum when `amrap` is true
+    pub reps: u8,
+    /// If as many reps as possible should be done for this set
+    pub amrap: bool,
+    /// The reps needed to beat the estimated one rep max record, amrap sets only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<u8>,
+}
+
+/// Everything done for one of the lifts in a week
+#[derive(Debug, Clone, Serialize)]
+pub struct Day {
+    pub lift: Lift,
+    pub warm_up: Vec<Set>,
+    pub work: Vec<Set>,
+}
+
+impl Day {
+    /// The warm up sets followed by the work sets
+    pub fn sets(&self) -> impl Iterator<Item = &Set> {
+        self.warm_up.iter().chain(self.work.iter())
+    }
+}
+
+/// One week of a cycle, with a day for each lift
+#[derive(Debug, Clone, Serialize)]
+pub struct Week {
+    /// The cycle this week is part of, starting at 1
+    pub cycle: u32,
+    /// The week of the cycle, 1 through 4 where 4 is the deload
+    pub number: u32,
+    /// The training maxes the sets were calculated from
+    pub training_max: TrainingMax,
+    /// What each lift's weights were rounded up to a multiple of
+    #[serde(skip)]
+    pub rounding: PerLift<Weight>,
+    pub days: Vec<Day>,
+}
+
+impl Week {
+    /// Calculate the sets for every lift in a week of a cycle, each weight is rounded
+    /// up to a multiple of that lift's `rounding` and no warm up set will be lighter than the `bar`
+    pub fn new(
+        cycle: u32,
+        number: u32,
+        training_max: &TrainingMax,
+        warm_up: &WarmUp,
+        bar: Weight,
+        rounding: &PerLift<Weight>,
+    ) -> Self {
+        let (percents, reps) = match number {
+            1 => (WEEK_ONE_PERCENTS, [5; 3]),
+            2 => (WEEK_TWO_PERCENTS, [3; 3]),
+            3 => (WEEK_THREE_PERCENTS, [5, 3, 1]),
+            _ => (WEEK_FOUR_PERCENTS, [5; 3]),
+        };
+        let days = Lift::ALL
+            .iter()
+            .map(|lift| {
+                let base = training_max.get(*lift);
+                let rounding = rounding.get(*lift);
+                let work: Vec<Set> = sets_from(base, percents, rounding)
+                    .iter()
+                    .zip(reps.iter())
+                    .enumerate()
+                    .map(|(idx, (weight, reps))| Set {
+                        weight: *weight,
+                        reps: *reps,
+                        amrap: number != 4 && idx == 2,
+                        target: None,
+                    })
+                    .collect();
+                Day {
+                    lift: *lift,
+                    warm_up: warm_up.sets_from(base, bar, rounding, work[0].weight),
+                    work,
+                }
+            })
+            .collect();
+        Self {
+            cycle,
+            number,
+            training_max: *training_max,
+            rounding: *rounding,
+            days,
+        }
+    }
+
+    pub fn day(&self, lift: Lift) -> Option<&Day> {
+        self.days.iter().find(|day| day.lift == lift)
+    }
+}
+
+/// A full 5/3/1 plan, 4 weeks for each cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct Program {
+    pub weeks: Vec<Week>,
+}
+
+impl Program {
+    pub fn builder(training_max: TrainingMax) -> ProgramBuilder {
+        ProgramBuilder::new(training_max)
+    }
+}
+
+/// Builds a `Program` from the starting training maxes
+///
+/// ```
+/// use five_three_one::plan::{Program, TrainingMax, WarmUp};
+///
+/// let program = Program::builder(TrainingMax::from_one_rep_maxes(215.0, 200.0, 125.0, 95.0))
+///     .cycles(2)
+///     .warm_up(WarmUp::none())
+///     .build();
+/// assert_eq!(program.weeks.len(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProgramBuilder {
+    training_max: TrainingMax,
+    cycles: u32,
+    warm_up: WarmUp,
+    bar: Weight,
+    rounding: PerLift<Weight>,
+    increments: PerLift<Weight>,
+    records: Records,
+}
+
+impl ProgramBuilder {
+    pub fn new(training_max: TrainingMax) -> Self {
+        Self {
+            training_max,
+            cycles: 1,
+            warm_up: WarmUp::default(),
+            bar: BAR,
+            rounding: PerLift::all(DEFAULT_ROUNDING),
+            increments: DEFAULT_INCREMENTS,
+            records: Records::default(),
+        }
+    }
+
+    /// Replace the training maxes the first cycle is calculated from
+    pub fn training_max(mut self, training_max: TrainingMax) -> Self {
+        self.training_max = training_max;
+        self
+    }
+
+    /// How many 4 week cycles to generate, defaults to 1
+    pub fn cycles(mut self, cycles: u32) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// The warm up sets done before the work sets, defaults to `WarmUp::default()`
+    pub fn warm_up(mut self, warm_up: WarmUp) -> Self {
+        self.warm_up = warm_up;
+        self
+    }
+
+    /// The weight of the empty bar, defaults to `BAR`
+    pub fn bar(mut self, bar: Weight) -> Self {
+        self.bar = bar;
+        self
+    }
+
+    /// Round every weight for `lift` up to a multiple of `rounding`, defaults to `DEFAULT_ROUNDING`
+    pub fn rounding(mut self, lift: Lift, rounding: Weight) -> Self {
+        self.rounding.set(lift, rounding);
+        self
+    }
+
+    /// How much the training max for `lift` goes up each cycle, defaults to `DEFAULT_INCREMENTS`
+    pub fn increment(mut self, lift: Lift, increment: Weight) -> Self {
+        self.increments.set(lift, increment);
+        self
+    }
+
+    /// The personal records used to show the reps needed for a new record on each amrap set
+    pub fn records(mut self, records: Records) -> Self {
+        self.records = records;
+        self
+    }
+
+    pub fn build(self) -> Program {
+        let mut training_max = self.training_max;
+        let mut weeks = Vec::with_capacity(self.cycles as usize * 4);
+        for cycle in 1..=self.cycles {
+            for number in 1..=4 {
+                let mut week = Week::new(
+                    cycle,
+                    number,
+                    &training_max,
+                    &self.warm_up,
+                    self.bar,
+                    &self.rounding,
+                );
+                self.records.apply(&mut week);
+                weeks.push(week);
+            }
+            training_max = training_max.next_cycle_by(&self.increments);
+        }
+        Program { weeks }
+    }
+}
+
+/// The warm up sets done before the work sets of each day
+#[derive(Debug, Clone)]
+pub struct WarmUp(Vec<WarmUpSet>);
+
+#[derive(Debug, Clone, Copy)]
+struct WarmUpSet {
+    /// The percent of the training max, `None` for an empty bar
+    percent: Option<f32>,
+    reps: u8,
+}
+
+impl WarmUp {
+    /// No warm up sets at all
+    pub fn none() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The warm up sets for a training max of `base`, any set that would be as heavy
+    /// as the first work set is left out, like the 50% and 60% sets in the deload week
+    fn sets_from(
+        &self,
+        base: Weight,
+        bar: Weight,
+        rounding: Weight,
+        first_work: Weight,
+    ) -> Vec<Set> {
+        self.0
+            .iter()
+            .map(|set| Set {
+                weight: match set.percent {
+                    Some(percent) => percent_of(base, percent, rounding).max(bar),
+                    None => bar,
+                },
+                reps: set.reps,
+                amrap: false,
+                target: None,
+            })
+            .filter(|set| set.weight < first_work)
+            .collect()
+    }
+}
+
+impl Default for WarmUp {
+    /// 5 reps at 40%, 5 reps at 50% and 3 reps at 60% of the training max
+    fn default() -> Self {
+        DEFAULT_WARM_UP.parse().expect("Invalid default warm up")
+    }
+}
+
+/// The default warm up scheme, in the same format `WarmUp` is parsed from
+pub const DEFAULT_WARM_UP: &str = "40x5,50x5,60x3";
+const DEFAULT_WARM_UP_REPS: u8 = 5;
+
+impl FromStr for WarmUp {
+    type Err = String;
+
+    /// Parse a comma separated list of `<percent>x<reps>`, where `bar`
+    /// can be used as the percent for an empty bar
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("none") {
+            return Ok(Self::none());
+        }
+        let mut sets = Vec::new();
+        for set in s.split(',').map(str::trim).filter(|set| !set.is_empty()) {
+            let mut parts = set.split('x');
+            let weight = parts.next().unwrap_or_default();
+            let percent = if weight.eq_ignore_ascii_case("bar") {
+                None
+            } else {
+                let percent: f32 = weight
+                    .parse()
+                    .map_err(|_| format!("Warm up percent must be a number: {:?}", set))?;
+                Some(percent / 100.0)
+            };
+            let reps = match parts.next() {
+                Some(reps) => reps
+                    .parse()
+                    .map_err(|_| format!("Warm up reps must be a number: {:?}", set))?,
+                None => DEFAULT_WARM_UP_REPS,
+            };
+            sets.push(WarmUpSet { percent, reps });
+        }
+        Ok(Self(sets))
+    }
+}
+
+const WEEK_ONE_PERCENTS: [f32; 3] = [0.65, 0.75, 0.85];
+const WEEK_TWO_PERCENTS: [f32; 3] = [0.70, 0.80, 0.90];
+const WEEK_THREE_PERCENTS: [f32; 3] = [0.75, 0.85, 0.95];
+const WEEK_FOUR_PERCENTS: [f32; 3] = [0.5, 0.5, 0.5];
+
+/// The weights for 3 sets at the provided percentages of a training max,
+/// rounded up to a multiple of `rounding`
+pub fn sets_from(base: Weight, percents: [f32; 3], rounding: Weight) -> [Weight; 3] {
+    percents.map(|percent| percent_of(base, percent, rounding))
+}
+
+/// `percent` (as a fraction) of `base`, rounded up to a multiple of `rounding`
+pub fn percent_of(base: Weight, percent: f32, rounding: Weight) -> Weight {
+    base.times(percent).round_up_to(rounding)
+}
+
+/// Round up to the next multiple of 5
+pub fn round_weight(v: f32) -> Weight {
+    Weight::from(v).round_up_to(DEFAULT_ROUNDING)
+}
+
+/// Estimate a one rep max from a set of `reps` at `weight`
+pub fn one_rep_max(weight: f32, reps: u8) -> f32 {
+    (weight * reps as f32 * 0.0333) + weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn week(number: u32, warm_up: &str) -> Week {
+        Week::new(
+            1,
+            number,
+            &TrainingMax::from_one_rep_maxes(405.0, 455.0, 275.0, 175.0),
+            &warm_up.parse().unwrap(),
+            BAR,
+            &PerLift::all(DEFAULT_ROUNDING),
+        )
+    }
+
+    #[test]
+    fn warm_ups_are_lighter_than_the_deload() {
+        let week = week(4, "bar,40x5,50x5,60x3");
+        for day in &week.days {
+            let first_work = day.work[0].weight;
+            assert!(!day.warm_up.is_empty(), "{:?} has no warm up", day.lift);
+            for set in &day.warm_up {
+                assert!(
+                    set.weight < first_work,
+                    "{:?} warms up with {} before {}",
+                    day.lift,
+                    set.weight,
+                    first_work
+                );
+            }
+        }
+        // 40% of the 365 squat training max is 150, 50% and 60% are left out
+        let squat = week.day(Lift::Squat).unwrap();
+        let warm_up: Vec<Weight> = squat.warm_up.iter().map(|set| set.weight).collect();
+        assert_eq!(warm_up, vec![BAR, Weight::from(150.0)]);
+        assert_eq!(squat.work[0].weight, Weight::from(185.0));
+    }
+
+    #[test]
+    fn warm_ups_are_kept_before_heavier_work() {
+        for number in 1..=3 {
+            let week = week(number, "40x5,50x5,60x3");
+            for day in &week.days {
+                assert_eq!(day.warm_up.len(), 3, "week {} {:?}", number, day.lift);
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+    iter,
+    path::Path,
+};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file,
+    weight::Weight,
+};
+
+static DEFAULT_WEIGHTS: &str = include_str!("default_weights.toml");
+
+/// The weight of an empty bar
+pub const BAR: Weight = Weight::from_thousandths(45_000);
+const SIDES: u32 = 2;
+
+/// The plates you own, each plate size with how many
+/// of them can be loaded on one side of the bar
+#[derive(Debug, Clone)]
+pub struct PlateInventory {
+    bar: Weight,
+    collar: Weight,
+    plates: Vec<(Weight, u8)>,
+}
+
+impl Default for PlateInventory {
+    fn default() -> Self {
+        Self {
+            bar: BAR,
+            collar: Weight::ZERO,
+            plates: Vec::new(),
+        }
+    }
+}
+
+impl PlateInventory {
+    pub fn new(plates: &HashMap<Weight, u8>) -> Self {
+        let mut plates: Vec<(Weight, u8)> = plates.iter().map(|(p, ct)| (*p, *ct)).collect();
+        plates.sort_by_key(|(plate, _)| std::cmp::Reverse(*plate));
+        Self {
+            plates,
+            ..Default::default()
+        }
+    }
+
+    /// The weight of the empty bar, defaults to `BAR`
+    pub fn with_bar(mut self, bar: Weight) -> Self {
+        self.bar = bar;
+        self
+    }
+
+    /// The weight of each of the collars holding the plates on, defaults to 0
+    pub fn with_collar(mut self, collar: Weight) -> Self {
+        self.collar = collar;
+        self
+    }
+
+    /// The plates are taken from the largest number of each plate size used
+    /// on a side in any of the provided weights, and the bar from the weight
+    /// that doesn't need any plates
+    pub fn from_weights_map(weights: &WeightsMap) -> Self {
+        let mut counts: HashMap<Weight, u8> = HashMap::new();
+        for plates in weights.0.values() {
+            for (plate, count) in plates.iter().copied().counts() {
+                let current = counts.entry(plate).or_insert(0);
+                *current = (*current).max(count as u8);
+            }
+        }
+        let bar = weights
+            .0
+            .iter()
+            .filter(|(_, plates)| plates.is_empty())
+            .map(|(weight, _)| *weight)
+            .min()
+            .unwrap_or(BAR);
+        Self::new(&counts).with_bar(bar)
+    }
+
+    pub fn bar(&self) -> Weight {
+        self.bar
+    }
+
+    pub fn collar(&self) -> Weight {
+        self.collar
+    }
+
+    /// The weight loaded before any plates go on, the bar and both collars
+    pub fn empty(&self) -> Weight {
+        self.bar + self.collar * SIDES
+    }
+
+    /// Each plate size, heaviest first, with how many can be loaded on one side
+    pub fn plates(&self) -> &[(Weight, u8)] {
+        &self.plates
+    }
+
+    /// Every total weight these plates can reach, using the fewest plates for each
+    pub fn weights_map(&self) -> WeightsMap {
+        let flattened: Vec<Weight> = self
+            .plates
+            .iter()
+            .flat_map(|(weight, count)| iter::repeat_with(move || *weight).take(*count as _))
+            .collect();
+        weights_from_flattened_list(self.empty(), &flattened)
+    }
+}
+
+/// Calculate every total weight that can be reached with the plates provided,
+/// where each plate size has the number that can be loaded on one side
+pub fn calculate_all_weights_from(available: &HashMap<Weight, u8>) -> WeightsMap {
+    PlateInventory::new(available).weights_map()
+}
+
+/// The weights map for 4x45 1x35 1x25 2x10 1x5 1x2.5 plates
+pub fn default_weights() -> WeightsMap {
+    let swm = toml::from_str::<SeralizedWeightsMap>(DEFAULT_WEIGHTS)
+        .expect("Invalid default weights toml");
+    swm.try_into().expect("Invalid defaults weights")
+}
+
+fn weights_from_flattened_list(empty: Weight, plates: &[Weight]) -> WeightsMap {
+    let mut ret = HashMap::new();
+    ret.insert(empty, Vec::new());
+    for i in 0..plates.len() {
+        for mut set in plates.iter().copied().permutations(i + 1) {
+            set.sort_by(|l, r| r.cmp(l));
+            let (total_weight, plates) = sum_and_side(empty, &set);
+            insert_or_swap_if_fewer(total_weight, plates, &mut ret);
+        }
+    }
+    WeightsMap(ret)
+}
+
+/// The total weight when both sides are loaded with `plates`, `empty`
+/// is everything else on the bar such as the collars
+fn sum_and_side(empty: Weight, plates: &[Weight]) -> (Weight, Vec<Weight>) {
+    let side: Weight = plates.iter().sum();
+    let total_weight = side * SIDES + empty;
+    (total_weight, plates.to_vec())
+}
+
+fn insert_or_swap_if_fewer(
+    weight: Weight,
+    mut plates: Vec<Weight>,
+    current: &mut HashMap<Weight, Vec<Weight>>,
+) {
+    current
+        .entry(weight)
+        .and_modify(|current| {
+            if current.len() > plates.len() {
+                std::mem::swap(current, &mut plates);
+            }
+        })
+        .or_insert(plates);
+}
+
+/// A weights map as it is stored in a file, keyed by the total weight as a string
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SeralizedWeightsMap(pub HashMap<String, Vec<Weight>>);
+/// Each total weight that can be reached and the plates
+/// needed on each side of the bar to reach it
+pub struct WeightsMap(pub HashMap<Weight, Vec<Weight>>);
+
+impl Default for WeightsMap {
+    fn default() -> Self {
+        default_weights()
+    }
+}
+
+impl WeightsMap {
+    /// Read a weights map from a .toml, .json or .yaml file
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ser_map: SeralizedWeightsMap = file::read(path)?;
+        ser_map.try_into().map_err(|message| Error::Parse {
+            path: path.to_path_buf(),
+            line: None,
+            column: None,
+            message,
+        })
+    }
+}
+
+impl TryFrom<SeralizedWeightsMap> for WeightsMap {
+    type Error = String;
+
+    fn try_from(value: SeralizedWeightsMap) -> std::result::Result<Self, Self::Error> {
+        let mut ret = HashMap::new();
+        for (key, value) in value.0 {
+            let key: Weight = key.parse()?;
+            ret.insert(key, value);
+        }
+        Ok(Self(ret))
+    }
+}
+
+impl From<WeightsMap> for SeralizedWeightsMap {
+    fn from(map: WeightsMap) -> Self {
+        let mut ret = HashMap::new();
+        for (key, value) in map.0 {
+            ret.insert(key.to_string(), value);
+        }
+        SeralizedWeightsMap(ret)
+    }
+}
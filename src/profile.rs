@@ -0,0 +1,201 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file::{self, Format},
+    import::Unit,
+    inventory::Inventory,
+    log::Log,
+    plan::{Lift, PerLift, TrainingMax, WarmUp, DEFAULT_INCREMENTS, DEFAULT_ROUNDING},
+    plates::{default_weights, PlateInventory},
+    strength::Sex,
+    supports::Supports,
+    weight::Weight,
+};
+
+/// An athlete's training maxes, schedule and equipment, as it is stored in a profile file
+///
+/// Any relative paths are relative to the profile file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// The date of the first session
+    #[serde(deserialize_with = "file::date")]
+    pub start: NaiveDate,
+    /// The days of the week sessions are done on, each session is the next lift in the plan
+    #[serde(default = "default_schedule")]
+    pub schedule: Vec<Weekday>,
+    /// The warm up sets, in the same format as `generate --warm-up`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warm_up: Option<String>,
+    /// The inventory file listing your bars, plates and collars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inventory: Option<PathBuf>,
+    /// The name of the bar to use from the inventory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bar: Option<String>,
+    /// The weight of each collar, this replaces the collar in the inventory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collar: Option<Weight>,
+    /// The supporting work file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<PathBuf>,
+    /// The training log results are added to
+    #[serde(default = "default_log")]
+    pub log: PathBuf,
+    /// The personal records that are updated after each session
+    #[serde(default = "default_records")]
+    pub records: PathBuf,
+    /// The training max for each lift at the start of the first cycle
+    pub training_max: TrainingMax,
+    /// The rounding for any lift that isn't rounded to `DEFAULT_ROUNDING`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rounding: BTreeMap<Lift, Weight>,
+    /// The increment for any lift that doesn't use `DEFAULT_INCREMENTS`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub increments: BTreeMap<Lift, Weight>,
+    /// The athlete's bodyweight, used until one is recorded in the log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bodyweight: Option<Weight>,
+    /// Which coefficients to score relative strength with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sex: Option<Sex>,
+    /// The unit every weight is in, pounds when it isn't provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<Unit>,
+}
+
+/// Monday, Tuesday, Thursday and Friday
+fn default_schedule() -> Vec<Weekday> {
+    vec![Weekday::Mon, Weekday::Tue, Weekday::Thu, Weekday::Fri]
+}
+
+fn default_log() -> PathBuf {
+    PathBuf::from("log.toml")
+}
+
+fn default_records() -> PathBuf {
+    PathBuf::from("records.toml")
+}
+
+impl Profile {
+    /// Read a profile from a .toml, .json or .yaml file
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let mut profile: Self = file::read(path)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for path in profile
+            .inventory
+            .iter_mut()
+            .chain(profile.extra.iter_mut())
+            .chain(std::iter::once(&mut profile.log))
+            .chain(std::iter::once(&mut profile.records))
+        {
+            *path = dir.join(&*path);
+        }
+        Ok(profile)
+    }
+
+    /// Replace the training maxes of some lifts in the profile at `path`, the rest of
+    /// the profile is written back the way it was read but without any comments
+    pub fn save_training_maxes(path: &Path, training_maxes: &[(Lift, Weight)]) -> Result<()> {
+        let mut profile: Self = file::read(path)?;
+        for (lift, training_max) in training_maxes {
+            profile.training_max.set(*lift, *training_max);
+        }
+        file::write(path, Format::of(path)?.to_string(&profile)?)
+    }
+
+    /// The latest bodyweight recorded in the `log` on or before `date`, or the one in the profile
+    pub fn bodyweight(&self, log: &Log, date: NaiveDate) -> Option<Weight> {
+        log.bodyweight(date).or(self.bodyweight)
+    }
+
+    pub fn rounding(&self) -> PerLift<Weight> {
+        let mut rounding = PerLift::all(DEFAULT_ROUNDING);
+        for (lift, value) in &self.rounding {
+            rounding.set(*lift, *value);
+        }
+        rounding
+    }
+
+    pub fn increments(&self) -> PerLift<Weight> {
+        let mut increments = DEFAULT_INCREMENTS;
+        for (lift, value) in &self.increments {
+            increments.set(*lift, *value);
+        }
+        increments
+    }
+
+    pub fn warm_up(&self) -> Result<WarmUp> {
+        match &self.warm_up {
+            Some(warm_up) => warm_up.parse().map_err(|e| Error::Validation(vec![e])),
+            None => Ok(WarmUp::default()),
+        }
+    }
+
+    /// The plates from the inventory file, or the default plates
+    pub fn plates(&self) -> Result<PlateInventory> {
+        let plates = match &self.inventory {
+            Some(path) => {
+                let inventory = Inventory::from_path(path)?;
+                let bar = inventory.bar(self.bar.as_deref())?;
+                inventory.plate_inventory(bar)
+            }
+            None => PlateInventory::from_weights_map(&default_weights()),
+        };
+        Ok(match self.collar {
+            Some(collar) => plates.with_collar(collar),
+            None => plates,
+        })
+    }
+
+    pub fn supports(&self) -> Result<Supports> {
+        match &self.extra {
+            Some(path) => Supports::from_path(path),
+            None => Ok(Supports::default()),
+        }
+    }
+
+    /// How many sessions are scheduled before `date`, or `None`
+    /// if `date` is before the start or isn't on the schedule
+    pub fn session_number(&self, date: NaiveDate) -> Option<u32> {
+        if date < self.start || !self.schedule.contains(&date.weekday()) {
+            return None;
+        }
+        let days = (date - self.start).num_days();
+        let whole_weeks = days / 7;
+        let mut sessions = whole_weeks * self.sessions_per_week();
+        let mut day = self.start + Duration::weeks(whole_weeks);
+        while day < date {
+            if self.schedule.contains(&day.weekday()) {
+                sessions += 1;
+            }
+            day = day.succ();
+        }
+        Some(sessions as u32)
+    }
+
+    /// The first scheduled day after `date`, if anything is scheduled
+    pub fn next_session(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut day = date.max(self.start.pred()).succ();
+        for _ in 0..7 {
+            if self.schedule.contains(&day.weekday()) {
+                return Some(day);
+            }
+            day = day.succ();
+        }
+        None
+    }
+
+    fn sessions_per_week(&self) -> i64 {
+        let mut days = self.schedule.clone();
+        days.sort_by_key(|day| day.num_days_from_monday());
+        days.dedup();
+        days.len() as i64
+    }
+}
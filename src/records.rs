@@ -0,0 +1,141 @@
+use std::{collections::BTreeMap, path::Path};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file::{self, Format},
+    log::LoggedSession,
+    plan::{one_rep_max, Lift, Week},
+    weight::Weight,
+};
+
+/// The most reps that will be suggested to beat a record, any more than
+/// this and the weight is too light for the estimate to mean much
+const MAX_TARGET_REPS: u8 = 30;
+
+/// The personal records for each lift, as they are stored in a records file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Records(pub BTreeMap<Lift, LiftRecords>);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiftRecords {
+    /// The set with the highest estimated one rep max
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e1rm: Option<Record>,
+    /// The heaviest set for each number of reps
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rep_maxes: Vec<Record>,
+}
+
+/// A set that was done, with the day it was done on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Record {
+    pub weight: Weight,
+    pub reps: u8,
+    #[serde(deserialize_with = "file::date")]
+    pub date: NaiveDate,
+}
+
+impl Record {
+    /// The one rep max estimated from this set
+    pub fn e1rm(&self) -> f32 {
+        one_rep_max(self.weight.into(), self.reps)
+    }
+}
+
+impl Records {
+    /// Read the records from a .toml, .json or .yaml file, a file
+    /// that doesn't exist yet has no records
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match file::read(path) {
+            Err(Error::Read { source, .. }) if source.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Write the records to a .toml, .json or .yaml file, replacing what was there
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let text = Format::of(path)?.to_string(self)?;
+        file::write(path, text)
+    }
+
+    /// The best estimated one rep max for `lift`
+    pub fn e1rm(&self, lift: Lift) -> Option<f32> {
+        self.0
+            .get(&lift)
+            .and_then(|records| records.e1rm)
+            .map(|record| record.e1rm())
+    }
+
+    /// The fewest reps at `weight` that would beat the best estimated one rep max
+    /// for `lift`, `None` when there isn't a record yet or it is out of reach
+    pub fn target(&self, lift: Lift, weight: Weight) -> Option<u8> {
+        let best = self.e1rm(lift)?;
+        (1..=MAX_TARGET_REPS).find(|reps| one_rep_max(weight.into(), *reps) > best)
+    }
+
+    /// Set the target on each amrap set in `week`
+    pub fn apply(&self, week: &mut Week) {
+        for day in &mut week.days {
+            for set in day.work.iter_mut().filter(|set| set.amrap) {
+                set.target = self.target(day.lift, set.weight);
+            }
+        }
+    }
+
+    /// Add the work sets of a logged session, describing each record that was broken
+    pub fn update(&mut self, session: &LoggedSession) -> Vec<String> {
+        let records = self.0.entry(session.lift).or_default();
+        let mut e1rm = None;
+        let mut rep_maxes = BTreeMap::new();
+        for set in session
+            .sets
+            .iter()
+            .filter(|set| !set.warm_up && set.reps > 0)
+        {
+            let record = Record {
+                weight: set.weight,
+                reps: set.reps,
+                date: session.date,
+            };
+            if records.e1rm.is_none_or(|best| record.e1rm() > best.e1rm()) {
+                records.e1rm = Some(record);
+                e1rm = Some(record);
+            }
+            match records
+                .rep_maxes
+                .iter_mut()
+                .find(|best| best.reps == record.reps)
+            {
+                Some(best) if best.weight >= record.weight => continue,
+                Some(best) => *best = record,
+                None => records.rep_maxes.push(record),
+            }
+            rep_maxes.insert(record.reps, record.weight);
+        }
+        records.rep_maxes.sort_by_key(|record| record.reps);
+        // only the best of each kind is reported, when several sets in the session beat the same record
+        let name = session.lift.name();
+        e1rm.map(|record| {
+            format!(
+                "New {} estimated 1RM record: {} x{} is {}",
+                name,
+                record.weight,
+                record.reps,
+                (record.e1rm() * 10.0).round() / 10.0
+            )
+        })
+        .into_iter()
+        .chain(
+            rep_maxes
+                .into_iter()
+                .map(|(reps, weight)| format!("New {} {} rep max: {}", name, reps, weight)),
+        )
+        .collect()
+    }
+}
@@ -0,0 +1,344 @@
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    diagram::{plate_diagram, uneven_plate_diagram, PlateColors},
+    error::Result,
+    layout::{
+        Layout, PageSize, COMPONENT_HEIGHT, DAY_HEADER_HEIGHT, DIAGRAM_ROW_HEIGHT, WARM_UP_HEIGHT,
+    },
+    loading::{Loading, LoadingPlanner, PlateStep},
+    plan::{Day, Lift, Program, Set, Week},
+    supports::{Support, Supports},
+    terminal::conditioning_days,
+    weight::Weight,
+};
+
+static HTML: &str = include_str!("templates/plan.html");
+
+/// Which optional details should be included when rendering a plan
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    /// Draw the plates for each set using these colours, html only
+    pub diagrams: Option<PlateColors>,
+    /// Include the plates to add and remove before each set
+    pub changes: bool,
+    /// The paper size the html plan will be printed on
+    pub page_size: PageSize,
+    /// How many days to print on each page of the html plan
+    pub days_per_page: u8,
+    /// How many weeks to print side by side in plain text, `None` fits as many as `width` allows
+    pub columns: Option<usize>,
+    /// The width of the terminal plain text is printed to, when it's known
+    pub width: Option<usize>,
+    /// Highlight the amrap sets in plain text with colours
+    pub color: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            diagrams: None,
+            changes: false,
+            page_size: PageSize::Letter,
+            days_per_page: 2,
+            columns: None,
+            width: None,
+            color: false,
+        }
+    }
+}
+
+/// A week formatted for the html template, with the sets for each lift
+#[derive(Debug, Serialize, Clone)]
+pub struct RenderedWeek {
+    number: u32,
+    squat: Vec<RenderedSet>,
+    dead: Vec<RenderedSet>,
+    bench: Vec<RenderedSet>,
+    ohp: Vec<RenderedSet>,
+    reps: [u8; 3],
+    squat_warm_up: Vec<RenderedSet>,
+    dead_warm_up: Vec<RenderedSet>,
+    bench_warm_up: Vec<RenderedSet>,
+    ohp_warm_up: Vec<RenderedSet>,
+    squat_supports: Vec<RenderedSupport>,
+    dead_supports: Vec<RenderedSupport>,
+    bench_supports: Vec<RenderedSupport>,
+    ohp_supports: Vec<RenderedSupport>,
+    squat_jumps: Vec<String>,
+    dead_jumps: Vec<String>,
+    bench_jumps: Vec<String>,
+    ohp_jumps: Vec<String>,
+    squat_conditioning: Vec<String>,
+    dead_conditioning: Vec<String>,
+    bench_conditioning: Vec<String>,
+    ohp_conditioning: Vec<String>,
+    /// For example `Conditioning 2 hard, 2 easy`, when there's any conditioning
+    conditioning_days: Option<String>,
+}
+
+/// A set's weight and plates formatted for display
+#[derive(Debug, Serialize, Clone)]
+pub struct RenderedSet {
+    pub value: String,
+    pub side: String,
+    /// The prescribed reps, the minimum for an amrap set
+    pub reps: u8,
+    /// An inline svg drawing of one side of the bar, or both sides when they differ
+    pub diagram: Option<String>,
+    /// The plates to add or remove from the previous set
+    pub change: Option<String>,
+    /// The reps needed for a new estimated one rep max record
+    pub target: Option<u8>,
+}
+
+/// A supporting exercise formatted for the html template
+#[derive(Debug, Serialize, Clone)]
+pub struct RenderedSupport {
+    pub name: String,
+    /// The sets and reps, for example `5x10-15`
+    pub sets_reps: String,
+    /// The weight or `BW`, empty when there isn't one
+    pub load: String,
+    /// The plates for one side of the bar, for supporting work done with the bar
+    pub side: Option<String>,
+    /// The plates to add or remove from the previous set
+    pub change: Option<String>,
+}
+
+/// The sets for one lift's day, with the plates planned across all of them
+#[derive(Debug, Clone, Default)]
+pub struct RenderedDay {
+    pub warm_up: Vec<RenderedSet>,
+    pub work: Vec<RenderedSet>,
+    /// Each of the day's supporting exercises in order
+    pub supports: Vec<RenderedSupport>,
+}
+
+impl RenderedWeek {
+    pub fn new(
+        week: &Week,
+        supports: &Supports,
+        planner: &LoadingPlanner,
+        options: RenderOptions,
+    ) -> Self {
+        let render = |lift: Lift| {
+            week.day(lift)
+                .map(|day| render_day(day, &supports.for_day(lift, week), planner, options))
+                .unwrap_or_default()
+        };
+        let squat_day = render(Lift::Squat);
+        let dead_day = render(Lift::Dead);
+        let bench_day = render(Lift::Bench);
+        let ohp_day = render(Lift::Ohp);
+        let jumps = |lift: Lift| {
+            supports
+                .jumps_for_day(lift, week)
+                .iter()
+                .map(Support::describe)
+                .collect()
+        };
+        let conditioning = |lift: Lift| {
+            supports
+                .conditioning_for_day(lift, week)
+                .iter()
+                .map(Support::describe)
+                .collect()
+        };
+        let first_day = week.days.first();
+        let mut reps = [0; 3];
+        for (rep, set) in reps
+            .iter_mut()
+            .zip(first_day.iter().flat_map(|day| day.work.iter()))
+        {
+            *rep = set.reps;
+        }
+        Self {
+            number: week.number,
+            squat: squat_day.work,
+            dead: dead_day.work,
+            bench: bench_day.work,
+            ohp: ohp_day.work,
+            reps,
+            squat_warm_up: squat_day.warm_up,
+            dead_warm_up: dead_day.warm_up,
+            bench_warm_up: bench_day.warm_up,
+            ohp_warm_up: ohp_day.warm_up,
+            squat_supports: squat_day.supports,
+            dead_supports: dead_day.supports,
+            bench_supports: bench_day.supports,
+            ohp_supports: ohp_day.supports,
+            squat_jumps: jumps(Lift::Squat),
+            dead_jumps: jumps(Lift::Dead),
+            bench_jumps: jumps(Lift::Bench),
+            ohp_jumps: jumps(Lift::Ohp),
+            squat_conditioning: conditioning(Lift::Squat),
+            dead_conditioning: conditioning(Lift::Dead),
+            bench_conditioning: conditioning(Lift::Bench),
+            ohp_conditioning: conditioning(Lift::Ohp),
+            conditioning_days: if supports.conditioning.is_empty() {
+                None
+            } else {
+                Some(conditioning_days(supports, week))
+            },
+        }
+    }
+}
+
+/// Render the warm up, work and supporting sets for one lift, the plates are planned
+/// across the warm up, the work sets and any supporting work done with the bar
+pub fn render_day(
+    day: &Day,
+    supports: &[Support],
+    planner: &LoadingPlanner,
+    options: RenderOptions,
+) -> RenderedDay {
+    let weights: Vec<Weight> = day
+        .sets()
+        .map(|set| set.weight)
+        .chain(supports.iter().filter_map(Support::bar_weight))
+        .collect();
+    let mut loadings = planner.plan(&weights).into_iter();
+    let mut render_set = |set: &Set| {
+        let loading = loadings.next().flatten();
+        RenderedSet {
+            value: format!("{: >3}", set.weight),
+            side: format_side(loading.as_ref()),
+            reps: set.reps,
+            diagram: render_diagram(loading.as_ref(), options),
+            change: render_changes(loading.as_ref(), options),
+            target: set.target,
+        }
+    };
+    let warm_up = day.warm_up.iter().map(&mut render_set).collect();
+    let work = day.work.iter().map(&mut render_set).collect();
+    let supports = supports
+        .iter()
+        .map(|support| {
+            let loading = support.bar_weight().map(|_| loadings.next().flatten());
+            RenderedSupport {
+                name: support.name.clone(),
+                sets_reps: support.sets_reps(),
+                load: support.load_text().unwrap_or_default(),
+                side: loading
+                    .as_ref()
+                    .map(|loading| format_side(loading.as_ref())),
+                change: loading
+                    .as_ref()
+                    .and_then(|loading| render_changes(loading.as_ref(), options)),
+            }
+        })
+        .collect();
+    RenderedDay {
+        warm_up,
+        work,
+        supports,
+    }
+}
+
+/// An inline svg drawing of one side of the bar, or both sides when they differ
+fn render_diagram(loading: Option<&Loading>, options: RenderOptions) -> Option<String> {
+    let colors = options.diagrams?;
+    let loading = loading?;
+    Some(if loading.is_even() {
+        plate_diagram(&loading.left.plates, colors)
+    } else {
+        uneven_plate_diagram(&loading.left.plates, &loading.right.plates, colors)
+    })
+}
+
+/// The plates to add and remove before a set, when they're included
+fn render_changes(loading: Option<&Loading>, options: RenderOptions) -> Option<String> {
+    loading.filter(|_| options.changes).map(format_changes)
+}
+
+/// Render a program as a printable html page
+pub fn html(
+    program: &Program,
+    supports: &Supports,
+    planner: &LoadingPlanner,
+    options: RenderOptions,
+) -> Result<String> {
+    let mut ctx = tera::Context::new();
+    ctx.insert(
+        "weeks",
+        &program
+            .weeks
+            .iter()
+            .map(|w| RenderedWeek::new(w, supports, planner, options))
+            .collect::<Vec<_>>(),
+    );
+    let mut header_height = DAY_HEADER_HEIGHT;
+    let has_warm_up = program
+        .weeks
+        .iter()
+        .flat_map(|week| week.days.iter())
+        .any(|day| !day.warm_up.is_empty());
+    if has_warm_up {
+        header_height += WARM_UP_HEIGHT;
+    }
+    if options.diagrams.is_some() {
+        // the warm up sets get a row of diagrams of their own
+        header_height += DIAGRAM_ROW_HEIGHT * if has_warm_up { 2.0 } else { 1.0 };
+    }
+    if !supports.jumps.is_empty() {
+        header_height += COMPONENT_HEIGHT;
+    }
+    if !supports.conditioning.is_empty() {
+        header_height += COMPONENT_HEIGHT;
+    }
+    ctx.insert(
+        "layout",
+        &Layout::new(options.page_size, options.days_per_page, header_height),
+    );
+    Ok(tera::Tera::one_off(HTML, &ctx, false)?)
+}
+
+/// The plates for one side of the bar, for example `(45 10 2.5)`, or
+/// both sides when they differ, for example `(L 45 10 2.5 | R 45 10)`
+pub fn format_side(loading: Option<&Loading>) -> String {
+    let plates = |plates: &[Weight]| plates.iter().map(|f| f.to_string()).join(" ");
+    match loading {
+        Some(loading) if loading.is_even() => {
+            format!("({})", plates(&loading.left.plates))
+        }
+        Some(loading) => {
+            let side = |side: &[Weight]| {
+                if side.is_empty() {
+                    "-".to_string()
+                } else {
+                    plates(side)
+                }
+            };
+            format!(
+                "(L {} | R {})",
+                side(&loading.left.plates),
+                side(&loading.right.plates)
+            )
+        }
+        None => "(??)".to_string(),
+    }
+}
+
+/// The steps for both sides of the bar, prefixed with the side when they differ
+fn format_changes(loading: &Loading) -> String {
+    if loading.left.steps == loading.right.steps {
+        format_steps(&loading.left.steps)
+    } else {
+        format!(
+            "L {} | R {}",
+            format_steps(&loading.left.steps),
+            format_steps(&loading.right.steps)
+        )
+    }
+}
+
+/// Describe the steps to take to get from one set to the next,
+/// for example `remove 5, add 10, add 2.5`
+pub fn format_steps(steps: &[PlateStep]) -> String {
+    if steps.is_empty() {
+        return "no change".to_string();
+    }
+    steps.iter().map(|step| step.to_string()).join(", ")
+}
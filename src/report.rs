@@ -0,0 +1,456 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    chart::{bar_chart, line_chart, Series},
+    error::Result,
+    import::Unit,
+    log::Log,
+    plan::{Lift, TrainingMax},
+    records::{Record, Records},
+    stats::{Cycle, Period, Stats},
+    strength::{Points, Sex},
+    weight::Weight,
+};
+
+static HTML: &str = include_str!("templates/report.html");
+
+/// One lift's charts and records formatted for the report
+#[derive(Debug, Serialize)]
+struct ReportedLift {
+    name: &'static str,
+    /// An inline svg of the training max and estimated one rep max for each cycle
+    maxes: String,
+    /// An inline svg of the amrap reps done in each cycle
+    amraps: String,
+    e1rm: Option<ReportedRecord>,
+    rep_maxes: Vec<ReportedRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportedRecord {
+    weight: String,
+    reps: u8,
+    e1rm: String,
+    date: String,
+}
+
+impl From<Record> for ReportedRecord {
+    fn from(record: Record) -> Self {
+        Self {
+            weight: record.weight.to_string(),
+            reps: record.reps,
+            e1rm: round(record.e1rm()),
+            date: record.date.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ReportedStall {
+    name: &'static str,
+    e1rm: String,
+    /// For example `cycle 2`
+    cycle: String,
+}
+
+/// Who the report is for, to compare their lifts with their bodyweight
+#[derive(Debug, Clone, Copy)]
+pub struct Athlete {
+    pub bodyweight: Option<Weight>,
+    /// Points are only scored when this is known
+    pub sex: Option<Sex>,
+    /// The unit the log and bodyweight are in
+    pub unit: Unit,
+    /// The training maxes for lifts without one in the log
+    pub training_max: Option<TrainingMax>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportedRelative {
+    bodyweight: String,
+    unit: &'static str,
+    lifts: Vec<RelativeLift>,
+    totals: Vec<ReportedTotal>,
+    /// Totals can't be scored without knowing which coefficients to use
+    scored: bool,
+    /// An inline svg of each bodyweight in the log, when there's more than one
+    chart: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RelativeLift {
+    name: &'static str,
+    training_max: String,
+    training_max_multiple: String,
+    e1rm: String,
+    e1rm_multiple: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportedTotal {
+    name: &'static str,
+    total: String,
+    wilks: String,
+    dots: String,
+    ipf_gl: String,
+}
+
+/// The lifts in a powerlifting total
+const TOTAL: [Lift; 3] = [Lift::Squat, Lift::Bench, Lift::Dead];
+
+/// Render a printable html report of the `log`, charting each lift by cycle
+/// with a table of its records and any lifts that have stalled
+///
+/// The records are worked out from the log, so they don't depend on a records file.
+/// When the `athlete`'s bodyweight is known each lift is also compared to it.
+pub fn html(
+    log: &Log,
+    title: &str,
+    date: NaiveDate,
+    stall_cycles: u32,
+    athlete: &Athlete,
+) -> Result<String> {
+    let stats = Stats::new(log, Period::Cycle, stall_cycles);
+    let mut records = Records::default();
+    for session in &log.sessions {
+        records.update(session);
+    }
+    let lifts: Vec<ReportedLift> = Lift::ALL
+        .iter()
+        .filter_map(|lift| {
+            let rows: Vec<_> = stats.rows.iter().filter(|row| row.lift == *lift).collect();
+            if rows.is_empty() {
+                return None;
+            }
+            let labels: Vec<String> = rows
+                .iter()
+                .map(|row| match row.cycle {
+                    Cycle::Planned(cycle) => format!("C{}", cycle),
+                    Cycle::Unplanned(monday) => monday.format("%-d %b %y").to_string(),
+                })
+                .collect();
+            let maxes = line_chart(
+                &labels,
+                &[
+                    Series {
+                        name: "Training max",
+                        color: "#888",
+                        dashed: true,
+                        values: rows
+                            .iter()
+                            .map(|row| row.training_max.map(f32::from))
+                            .collect(),
+                    },
+                    Series {
+                        name: "Estimated 1RM",
+                        color: "#c62828",
+                        dashed: false,
+                        values: rows.iter().map(|row| row.e1rm).collect(),
+                    },
+                ],
+            );
+            let amraps = bar_chart(
+                &labels,
+                "AMRAP reps",
+                "#1565c0",
+                &rows.iter().map(|row| row.amrap_reps).collect::<Vec<_>>(),
+            );
+            let lift_records = records.0.get(lift);
+            Some(ReportedLift {
+                name: lift.name(),
+                maxes,
+                amraps,
+                e1rm: lift_records.and_then(|r| r.e1rm).map(ReportedRecord::from),
+                rep_maxes: lift_records
+                    .map(|r| {
+                        r.rep_maxes
+                            .iter()
+                            .copied()
+                            .map(ReportedRecord::from)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+    let stalls: Vec<ReportedStall> = stats
+        .stalls
+        .iter()
+        .map(|stall| ReportedStall {
+            name: stall.lift.name(),
+            e1rm: round(stall.e1rm),
+            cycle: stall.cycle.describe(),
+        })
+        .collect();
+    let relative = athlete.bodyweight.map(|bodyweight| {
+        let training_max = |lift: Lift| {
+            log.latest_training_max(lift)
+                .or_else(|| athlete.training_max.map(|tm| tm.get(lift)))
+        };
+        // the estimated 1 rep max from the most recent cycle
+        let e1rm = |lift: Lift| {
+            stats
+                .rows
+                .iter()
+                .rev()
+                .filter(|row| row.lift == lift)
+                .find_map(|row| row.e1rm)
+                .map(Weight::from)
+        };
+        let multiple = |weight: Option<Weight>| match weight {
+            Some(weight) => format!(
+                "{:.2}x",
+                weight.thousandths() as f64 / bodyweight.thousandths() as f64
+            ),
+            None => "-".to_string(),
+        };
+        let shown = |weight: Option<Weight>| {
+            weight
+                .map(|weight| weight.to_string())
+                .unwrap_or_else(|| "-".into())
+        };
+        let lifts = Lift::ALL
+            .iter()
+            .map(|&lift| RelativeLift {
+                name: lift.name(),
+                training_max: shown(training_max(lift)),
+                training_max_multiple: multiple(training_max(lift)),
+                e1rm: shown(e1rm(lift)),
+                e1rm_multiple: multiple(e1rm(lift)),
+            })
+            .collect();
+        let mut totals = Vec::new();
+        if let Some(sex) = athlete.sex {
+            let maxes = [
+                (
+                    "Training max",
+                    TOTAL.iter().map(|lift| training_max(*lift)).sum(),
+                ),
+                ("Estimated 1RM", TOTAL.iter().map(|lift| e1rm(*lift)).sum()),
+            ];
+            for (name, total) in maxes {
+                if let Some(total) = total {
+                    let points = Points::new(
+                        athlete.unit.convert(total, Unit::Kg),
+                        athlete.unit.convert(bodyweight, Unit::Kg),
+                        sex,
+                    );
+                    totals.push(ReportedTotal {
+                        name,
+                        total: total.to_string(),
+                        wilks: round(points.wilks),
+                        dots: round(points.dots),
+                        ipf_gl: round(points.ipf_gl),
+                    });
+                }
+            }
+        }
+        let chart = if log.bodyweights.len() > 1 {
+            let labels: Vec<String> = log
+                .bodyweights
+                .iter()
+                .map(|weighing| weighing.date.format("%-d %b").to_string())
+                .collect();
+            Some(line_chart(
+                &labels,
+                &[Series {
+                    name: "Bodyweight",
+                    color: "#2e7d32",
+                    dashed: false,
+                    values: log
+                        .bodyweights
+                        .iter()
+                        .map(|w| Some(w.weight.into()))
+                        .collect(),
+                }],
+            ))
+        } else {
+            None
+        };
+        ReportedRelative {
+            bodyweight: bodyweight.to_string(),
+            unit: athlete.unit.name(),
+            lifts,
+            totals,
+            scored: athlete.sex.is_some(),
+            chart,
+        }
+    });
+    let mut ctx = tera::Context::new();
+    ctx.insert("title", title);
+    ctx.insert("relative", &relative);
+    ctx.insert("date", &date.format("%-d %B %Y").to_string());
+    ctx.insert("lifts", &lifts);
+    ctx.insert("stalls", &stalls);
+    ctx.insert("stall_cycles", &stall_cycles);
+    Ok(tera::Tera::one_off(HTML, &ctx, true)?)
+}
+
+/// Round to one decimal place, formatted here since the template would show every digit of an f32
+fn round(value: f32) -> String {
+    ((value * 10.0).round() / 10.0).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        log::{LoggedSession, LoggedSet},
+        plan::one_rep_max,
+        weight::Weight,
+    };
+
+    /// A session from a plan with a warm up set and then a single amrap set
+    fn session(
+        day: &str,
+        lift: Lift,
+        cycle: u32,
+        week: u32,
+        weight: f32,
+        reps: u8,
+    ) -> LoggedSession {
+        LoggedSession {
+            date: day.parse().unwrap(),
+            lift,
+            cycle: Some(cycle),
+            week: Some(week),
+            training_max: Some(Weight::from(200.0)),
+            sets: vec![
+                LoggedSet {
+                    weight: Weight::from(135.0),
+                    reps: 10,
+                    planned: None,
+                    amrap: false,
+                    warm_up: true,
+                },
+                LoggedSet {
+                    weight: weight.into(),
+                    reps,
+                    planned: None,
+                    amrap: true,
+                    warm_up: false,
+                },
+            ],
+            supplemental: Vec::new(),
+        }
+    }
+
+    /// Three cycles of bench where only the first sets a new estimated 1RM, and one of squats
+    fn log() -> Log {
+        Log {
+            sessions: vec![
+                session("2026-01-05", Lift::Bench, 1, 1, 185.0, 8),
+                session("2026-01-07", Lift::Squat, 1, 1, 275.0, 5),
+                session("2026-01-19", Lift::Bench, 1, 3, 205.0, 3),
+                session("2026-02-02", Lift::Bench, 2, 1, 190.0, 5),
+                session("2026-03-02", Lift::Bench, 3, 1, 195.0, 4),
+            ],
+            bodyweights: Vec::new(),
+        }
+    }
+
+    fn report(log: &Log) -> String {
+        let athlete = Athlete {
+            bodyweight: None,
+            sex: None,
+            unit: Unit::Lb,
+            training_max: None,
+        };
+        html(log, "Report", "2026-03-09".parse().unwrap(), 2, &athlete).unwrap()
+    }
+
+    /// The cells of each row of the first table after `heading`
+    fn table_after(html: &str, heading: &str) -> Vec<Vec<String>> {
+        let start = html.find(heading).expect(heading);
+        let table = &html[start..];
+        let table = &table[..table.find("</table>").unwrap()];
+        table
+            .split("<tr")
+            .map(|row| {
+                row.split("<td>")
+                    .skip(1)
+                    .map(|cell| cell[..cell.find("</td>").unwrap()].to_string())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cells| !cells.is_empty())
+            .collect()
+    }
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn lists_the_records_from_the_work_sets() {
+        let html = report(&log());
+        let e1rm = round(one_rep_max(185.0, 8));
+        assert_eq!(
+            table_after(&html, "<h2>Bench</h2>"),
+            vec![
+                row(&["Estimated 1RM", "185", "8", &e1rm, "2026-01-05"]),
+                row(&[
+                    "3 rep max",
+                    "205",
+                    "3",
+                    &round(one_rep_max(205.0, 3)),
+                    "2026-01-19"
+                ]),
+                row(&[
+                    "4 rep max",
+                    "195",
+                    "4",
+                    &round(one_rep_max(195.0, 4)),
+                    "2026-03-02"
+                ]),
+                row(&[
+                    "5 rep max",
+                    "190",
+                    "5",
+                    &round(one_rep_max(190.0, 5)),
+                    "2026-02-02"
+                ]),
+                row(&["8 rep max", "185", "8", &e1rm, "2026-01-05"]),
+            ]
+        );
+        assert_eq!(
+            table_after(&html, "<h2>Squats</h2>"),
+            vec![
+                row(&[
+                    "Estimated 1RM",
+                    "275",
+                    "5",
+                    &round(one_rep_max(275.0, 5)),
+                    "2026-01-07"
+                ]),
+                row(&[
+                    "5 rep max",
+                    "275",
+                    "5",
+                    &round(one_rep_max(275.0, 5)),
+                    "2026-01-07"
+                ]),
+            ]
+        );
+        assert!(!html.contains("<h2>Deads</h2>"));
+    }
+
+    #[test]
+    fn lists_stalled_lifts_with_the_cycle_of_their_best() {
+        let html = report(&log());
+        assert_eq!(
+            table_after(&html, "<h2>Stalls</h2>"),
+            vec![row(&["Bench", &round(one_rep_max(185.0, 8)), "cycle 1"])]
+        );
+    }
+
+    #[test]
+    fn says_when_nothing_has_stalled() {
+        let mut log = log();
+        log.sessions
+            .push(session("2026-03-30", Lift::Bench, 4, 1, 200.0, 8));
+        let html = report(&log);
+        assert!(!html.contains(r#"<tr class="stalled">"#));
+        assert!(html.contains("No lift has gone 2 cycles without a new estimated 1RM."));
+    }
+}
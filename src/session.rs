@@ -0,0 +1,341 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::NaiveDate;
+
+use crate::{
+    error::{Error, Result},
+    loading::LoadingPlanner,
+    log::{Log, LoggedSession, LoggedSet, LoggedSupport},
+    plan::{Day, Lift, Set, Week},
+    profile::Profile,
+    render::{render_day, RenderOptions, RenderedSet},
+    supports::Support,
+    terminal::week_title,
+    weight::Weight,
+};
+
+/// The lift done on one of the days in a profile's schedule
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub date: NaiveDate,
+    pub lift: Lift,
+    /// The week of the plan this session is from, with the training maxes for its cycle
+    pub week: Week,
+    /// The jumps and throws done before the main lift
+    pub jumps: Vec<Support>,
+    /// The supporting work done after the main lift
+    pub supports: Vec<Support>,
+    /// The conditioning done after the supporting work
+    pub conditioning: Vec<Support>,
+}
+
+impl Session {
+    /// The session scheduled on `date`, or `None` when nothing is scheduled that day
+    ///
+    /// Each scheduled day is the next lift in the plan and the training
+    /// maxes for its cycle depend on the amrap sets in the `log`.
+    pub fn scheduled(
+        profile: &Profile,
+        log: &Log,
+        date: NaiveDate,
+        bar: Weight,
+    ) -> Result<Option<Self>> {
+        let number = match profile.session_number(date) {
+            Some(number) => number,
+            None => return Ok(None),
+        };
+        let lifts = Lift::ALL.len() as u32;
+        let cycle = number / (lifts * 4) + 1;
+        let week = number / lifts % 4 + 1;
+        let lift = Lift::ALL[(number % lifts) as usize];
+        let increments = profile.increments();
+        let mut training_max = profile.training_max;
+        for &lift in &Lift::ALL {
+            let start = profile.training_max.get(lift);
+            training_max.set(
+                lift,
+                log.training_max(lift, start, increments.get(lift), cycle),
+            );
+        }
+        let week = Week::new(
+            cycle,
+            week,
+            &training_max,
+            &profile.warm_up()?,
+            bar,
+            &profile.rounding(),
+        );
+        let supports = profile
+            .supports()?
+            .with_bodyweight(profile.bodyweight(log, date));
+        Ok(Some(Self {
+            date,
+            lift,
+            jumps: supports.jumps_for_day(lift, &week),
+            conditioning: supports.conditioning_for_day(lift, &week),
+            supports: supports.for_day(lift, &week),
+            week,
+        }))
+    }
+
+    pub fn day(&self) -> Option<&Day> {
+        self.week.day(self.lift)
+    }
+
+    pub fn training_max(&self) -> Weight {
+        self.week.training_max.get(self.lift)
+    }
+
+    /// Step through every set on the terminal, asking for the reps done on each
+    /// amrap set and resting for `rest` after each work and supporting set.
+    /// `None` if the session is stopped before the end
+    pub fn run(
+        &self,
+        planner: &LoadingPlanner,
+        rest: Option<Duration>,
+    ) -> Result<Option<LoggedSession>> {
+        let day = match self.day() {
+            Some(day) => day,
+            None => return Ok(None),
+        };
+        println!(
+            "{} - Cycle {}, {} - {}",
+            self.lift.name(),
+            self.week.cycle,
+            week_title(&self.week),
+            self.date.format("%A %-d %B %Y")
+        );
+        println!("Training max {}", self.training_max());
+        println!(
+            "Press enter after each set, or enter the reps done if they were different, s to skip a set or q to stop"
+        );
+        let mut prompt = Prompt::stdin();
+        let options = RenderOptions {
+            changes: true,
+            ..Default::default()
+        };
+        let rendered = render_day(day, &self.supports, planner, options);
+        let (warm_up, work) = (&rendered.warm_up, &rendered.work);
+        let mut sets = Vec::new();
+        let mut supplemental = Vec::new();
+        if !ask_each(
+            &mut prompt,
+            "Jumps and throws",
+            &self.jumps,
+            &mut supplemental,
+        )? {
+            return Ok(None);
+        }
+        if !warm_up.is_empty() {
+            println!("\nWarm up");
+        }
+        for (rendered, planned) in warm_up.iter().zip(&day.warm_up) {
+            match prompt.ask(&describe(rendered, planned), planned.amrap)? {
+                Answer::Quit => return Ok(None),
+                Answer::Skip => {}
+                Answer::Done => sets.push(logged_set(planned, planned.reps, true)),
+                Answer::Reps(reps) => sets.push(logged_set(planned, reps, true)),
+            }
+        }
+        println!("\nWork");
+        let rests = work.len() + self.supports.len();
+        for (idx, (rendered, planned)) in work.iter().zip(&day.work).enumerate() {
+            match prompt.ask(&describe(rendered, planned), planned.amrap)? {
+                Answer::Quit => return Ok(None),
+                Answer::Skip => {}
+                Answer::Done => sets.push(logged_set(planned, planned.reps, false)),
+                Answer::Reps(reps) => sets.push(logged_set(planned, reps, false)),
+            }
+            if idx + 1 < rests {
+                prompt.rest(rest)?;
+            }
+        }
+        if !self.supports.is_empty() {
+            println!("\nSupplemental");
+        }
+        for (idx, (support, plates)) in self.supports.iter().zip(&rendered.supports).enumerate() {
+            let mut description = format!("  {}", support.describe());
+            if let Some(implement) = &support.implement {
+                description.push_str(&format!(" ({})", implement));
+            }
+            if let Some(side) = &plates.side {
+                description.push_str(&format!(" {}", side));
+            }
+            if let Some(change) = &plates.change {
+                description.push_str(&format!(" {}", change));
+            }
+            match prompt.ask(&description, false)? {
+                Answer::Quit => return Ok(None),
+                Answer::Skip => {}
+                Answer::Done | Answer::Reps(_) => supplemental.push(LoggedSupport {
+                    name: support.name.clone(),
+                    weight: support.weight,
+                }),
+            }
+            if work.len() + idx + 1 < rests {
+                prompt.rest(rest)?;
+            }
+        }
+        if !ask_each(
+            &mut prompt,
+            "Conditioning",
+            &self.conditioning,
+            &mut supplemental,
+        )? {
+            return Ok(None);
+        }
+        Ok(Some(LoggedSession {
+            date: self.date,
+            lift: self.lift,
+            cycle: Some(self.week.cycle),
+            week: Some(self.week.number),
+            training_max: Some(self.training_max()),
+            sets,
+            supplemental,
+        }))
+    }
+}
+
+/// Ask whether each of the `exercises` was done under a `heading`, adding the
+/// ones that were to `done`. `false` if the session was stopped
+fn ask_each(
+    prompt: &mut Prompt,
+    heading: &str,
+    exercises: &[Support],
+    done: &mut Vec<LoggedSupport>,
+) -> Result<bool> {
+    if !exercises.is_empty() {
+        println!("\n{}", heading);
+    }
+    for exercise in exercises {
+        match prompt.ask(&format!("  {}", exercise.describe()), false)? {
+            Answer::Quit => return Ok(false),
+            Answer::Skip => {}
+            Answer::Done | Answer::Reps(_) => done.push(LoggedSupport {
+                name: exercise.name.clone(),
+                weight: exercise.weight,
+            }),
+        }
+    }
+    Ok(true)
+}
+
+/// A set as it is listed in the plain text plan
+fn describe(rendered: &RenderedSet, planned: &Set) -> String {
+    let mut description = format!(
+        "  {}{} x{}{}",
+        rendered.value,
+        rendered.side,
+        planned.reps,
+        if planned.amrap { "+" } else { "" }
+    );
+    if let Some(target) = rendered.target {
+        description.push_str(&format!(" ({} for a PR)", target));
+    }
+    if let Some(change) = &rendered.change {
+        description.push_str(&format!(" {}", change));
+    }
+    description
+}
+
+fn logged_set(planned: &Set, reps: u8, warm_up: bool) -> LoggedSet {
+    LoggedSet {
+        weight: planned.weight,
+        reps,
+        planned: Some(planned.reps),
+        amrap: planned.amrap,
+        warm_up,
+    }
+}
+
+/// What was entered after a set
+enum Answer {
+    /// Enter was pressed without anything else
+    Done,
+    Reps(u8),
+    Skip,
+    Quit,
+}
+
+/// Reads answers from stdin on another thread, so a rest can be cut short
+struct Prompt {
+    lines: Receiver<io::Result<String>>,
+}
+
+impl Prompt {
+    fn stdin() -> Self {
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { lines }
+    }
+
+    /// Ask until a valid answer is entered, when `required` a number of reps must be entered.
+    /// Stopping the input is the same as quitting
+    fn ask(&mut self, description: &str, required: bool) -> Result<Answer> {
+        let mut prompt = if required {
+            format!("{} reps done > ", description)
+        } else {
+            format!("{} > ", description)
+        };
+        loop {
+            print!("{}", prompt);
+            io::stdout().flush().map_err(Error::Terminal)?;
+            let line = match self.lines.recv() {
+                Ok(line) => line.map_err(Error::Terminal)?,
+                Err(_) => {
+                    println!();
+                    return Ok(Answer::Quit);
+                }
+            };
+            match line.trim().to_lowercase().as_str() {
+                "q" | "quit" => return Ok(Answer::Quit),
+                "s" | "skip" => return Ok(Answer::Skip),
+                "" if !required => return Ok(Answer::Done),
+                reps => {
+                    if let Ok(reps) = reps.parse() {
+                        return Ok(Answer::Reps(reps));
+                    }
+                }
+            }
+            prompt = "  Enter the number of reps done, s to skip or q to stop > ".to_string();
+        }
+    }
+
+    /// Count down `rest` on one line, pressing enter ends it early
+    fn rest(&mut self, rest: Option<Duration>) -> Result<()> {
+        let end = match rest {
+            Some(rest) => Instant::now() + rest,
+            None => return Ok(()),
+        };
+        loop {
+            let left = end.saturating_duration_since(Instant::now());
+            if left == Duration::ZERO {
+                break;
+            }
+            let seconds = (left.as_millis() as u64).div_ceil(1000);
+            print!(
+                "\r  Rest {}:{:02}, press enter to skip ",
+                seconds / 60,
+                seconds % 60
+            );
+            io::stdout().flush().map_err(Error::Terminal)?;
+            match self.lines.recv_timeout(left.min(Duration::from_secs(1))) {
+                Err(RecvTimeoutError::Timeout) => {}
+                Ok(_) | Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        println!("\r  Rest over{}", " ".repeat(30));
+        Ok(())
+    }
+}
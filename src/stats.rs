@@ -0,0 +1,449 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use chrono::{Datelike, NaiveDate};
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::{
+    log::{Log, LoggedSession},
+    plan::{one_rep_max, Lift},
+    weight::Weight,
+};
+
+/// How much of the log each row of the stats covers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Period {
+    Week,
+    Cycle,
+}
+
+impl FromStr for Period {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "week" => Self::Week,
+            "cycle" => Self::Cycle,
+            _ => return Err(format!("Unknown period: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+/// The cycle a row of the stats covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(untagged)]
+pub enum Cycle {
+    /// A cycle of a plan, as it was logged
+    Planned(u32),
+    /// Sessions that weren't logged from a plan, like imported ones, in
+    /// the 4 weeks starting on this Monday
+    Unplanned(NaiveDate),
+}
+
+impl Cycle {
+    /// For example `cycle 2`, or `the 4 weeks from 2026-01-05`
+    pub fn describe(self) -> String {
+        match self {
+            Self::Planned(cycle) => format!("cycle {}", cycle),
+            Self::Unplanned(monday) => format!("the 4 weeks from {}", monday),
+        }
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Planned(cycle) => cycle.fmt(f),
+            Self::Unplanned(monday) => monday.fmt(f),
+        }
+    }
+}
+
+/// The totals for one lift over a week or cycle of the log
+#[derive(Debug, Clone, Serialize)]
+pub struct Row {
+    pub lift: Lift,
+    pub cycle: Cycle,
+    /// The week of the cycle, `None` when the row covers the whole cycle
+    pub week: Option<u32>,
+    /// The first day logged in the period
+    pub date: NaiveDate,
+    /// The last training max logged in the period
+    pub training_max: Option<Weight>,
+    /// The best one rep max estimated from the work sets
+    pub e1rm: Option<f32>,
+    /// The reps done on the amrap sets
+    pub amrap_reps: u32,
+    /// The number of work sets done
+    pub sets: u32,
+    /// The reps done on the work sets
+    pub volume: u32,
+    /// The weight lifted on the work sets, weight times reps
+    pub tonnage: Weight,
+    /// If the estimated one rep max hasn't gone up in the cycles up to this one
+    pub stalled: bool,
+}
+
+/// A lift whose estimated one rep max hasn't gone up in a while
+#[derive(Debug, Clone, Serialize)]
+pub struct Stall {
+    pub lift: Lift,
+    /// The best estimated one rep max, from before the stall
+    pub e1rm: f32,
+    /// The cycle the best estimated one rep max is from
+    pub cycle: Cycle,
+}
+
+/// The trends in a training log, a row for each lift in each week or cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct Stats {
+    pub rows: Vec<Row>,
+    /// The lifts that are stalled in their most recent cycle
+    pub stalls: Vec<Stall>,
+}
+
+impl Stats {
+    /// Work out the stats for every lift in the `log`, a lift is stalled when none
+    /// of its last `stall_cycles` cycles beat the estimated one rep max from before them
+    ///
+    /// Sessions that weren't logged from a plan are kept apart from the plan's cycles, in 4 week
+    /// cycles counted from the week of the first of them. The cycles of each lift are in the order
+    /// they were started, so imported history comes before a plan that followed it.
+    pub fn new(log: &Log, period: Period, stall_cycles: u32) -> Self {
+        let first_unplanned = log
+            .sessions
+            .iter()
+            .filter(|session| session.cycle.is_none() || session.week.is_none())
+            .map(|session| session.date)
+            .min();
+        let mut periods: BTreeMap<(Lift, Cycle, Option<u32>), Vec<&LoggedSession>> =
+            BTreeMap::new();
+        for session in &log.sessions {
+            let (cycle, week) = cycle_and_week(session, first_unplanned);
+            let week = match period {
+                Period::Week => Some(week),
+                Period::Cycle => None,
+            };
+            periods
+                .entry((session.lift, cycle, week))
+                .or_default()
+                .push(session);
+        }
+        let mut rows: Vec<Row> = periods
+            .into_iter()
+            .map(|((lift, cycle, week), sessions)| Row::new(lift, cycle, week, &sessions))
+            .collect();
+        rows.sort_by_key(|row| (row.lift, row.date, row.cycle, row.week));
+        // the best estimated one rep max in each cycle of a lift, in the order the cycles started
+        let mut bests: BTreeMap<Lift, IndexMap<Cycle, f32>> = BTreeMap::new();
+        for row in &rows {
+            if let Some(e1rm) = row.e1rm {
+                let best = bests
+                    .entry(row.lift)
+                    .or_default()
+                    .entry(row.cycle)
+                    .or_insert(e1rm);
+                *best = best.max(e1rm);
+            }
+        }
+        for row in &mut rows {
+            if let Some(bests) = bests.get(&row.lift) {
+                row.stalled = bests
+                    .get_index_of(&row.cycle)
+                    .and_then(|idx| stalled(bests, idx, stall_cycles))
+                    .is_some();
+            }
+        }
+        let stalls = bests
+            .iter()
+            .filter_map(|(lift, bests)| {
+                let last = bests.len().checked_sub(1)?;
+                let (cycle, e1rm) = stalled(bests, last, stall_cycles)?;
+                Some(Stall {
+                    lift: *lift,
+                    e1rm,
+                    cycle,
+                })
+            })
+            .collect();
+        Self { rows, stalls }
+    }
+
+    /// The rows as a plain text table, with a line for each stalled lift
+    pub fn table(&self) -> String {
+        let weeks = self.rows.iter().any(|row| row.week.is_some());
+        let mut header = vec!["Lift", "Cycle"];
+        if weeks {
+            header.push("Week");
+        }
+        header.extend(&["Date", "TM", "e1RM", "AMRAP", "Sets", "Reps", "Tonnage", ""]);
+        let mut lines: Vec<Vec<String>> = vec![header.iter().map(|s| s.to_string()).collect()];
+        for row in &self.rows {
+            let mut line = vec![row.lift.name().to_string(), row.cycle.to_string()];
+            if weeks {
+                line.push(row.week.map(|w| w.to_string()).unwrap_or_default());
+            }
+            line.extend(vec![
+                row.date.to_string(),
+                optional(row.training_max),
+                optional(row.e1rm.map(round)),
+                row.amrap_reps.to_string(),
+                row.sets.to_string(),
+                row.volume.to_string(),
+                row.tonnage.to_string(),
+                if row.stalled { "stalled" } else { "" }.to_string(),
+            ]);
+            lines.push(line);
+        }
+        let mut widths = vec![0; lines[0].len()];
+        for line in &lines {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut out = String::new();
+        for line in &lines {
+            let mut text = String::new();
+            for (idx, (cell, width)) in line.iter().zip(&widths).enumerate() {
+                // the lift names are on the left and the numbers are on the right
+                if idx == 0 {
+                    text.push_str(&format!("{: <1$}", cell, width));
+                } else {
+                    text.push_str(&format!("  {: >1$}", cell, width));
+                }
+            }
+            out.push_str(text.trim_end());
+            out.push('\n');
+        }
+        for stall in &self.stalls {
+            out.push_str(&format!(
+                "\n{} has stalled, the best estimated 1RM is still {} from {}",
+                stall.lift.name(),
+                round(stall.e1rm),
+                stall.cycle.describe()
+            ));
+        }
+        if !self.stalls.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The rows as comma separated values, with a header line
+    pub fn csv(&self) -> String {
+        let mut out =
+            "lift,cycle,week,date,training_max,e1rm,amrap_reps,sets,volume,tonnage,stalled\n"
+                .to_string();
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{}\n",
+                String::from(row.lift),
+                row.cycle,
+                row.week.map(|w| w.to_string()).unwrap_or_default(),
+                row.date,
+                optional(row.training_max),
+                optional(row.e1rm.map(round)),
+                row.amrap_reps,
+                row.sets,
+                row.volume,
+                row.tonnage,
+                row.stalled
+            ));
+        }
+        out
+    }
+}
+
+impl Row {
+    fn new(lift: Lift, cycle: Cycle, week: Option<u32>, sessions: &[&LoggedSession]) -> Self {
+        let work = sessions
+            .iter()
+            .flat_map(|session| session.sets.iter())
+            .filter(|set| !set.warm_up && set.reps > 0);
+        let mut row = Self {
+            lift,
+            cycle,
+            week,
+            date: sessions
+                .iter()
+                .map(|session| session.date)
+                .fold(sessions[0].date, NaiveDate::min),
+            training_max: sessions
+                .iter()
+                .filter_map(|session| session.training_max)
+                .next_back(),
+            e1rm: None,
+            amrap_reps: 0,
+            sets: 0,
+            volume: 0,
+            tonnage: Weight::ZERO,
+            stalled: false,
+        };
+        for set in work {
+            let e1rm = round(one_rep_max(set.weight.into(), set.reps));
+            row.e1rm = Some(row.e1rm.map_or(e1rm, |best| best.max(e1rm)));
+            if set.amrap {
+                row.amrap_reps += set.reps as u32;
+            }
+            row.sets += 1;
+            row.volume += set.reps as u32;
+            row.tonnage += set.weight * set.reps as u32;
+        }
+        row
+    }
+}
+
+/// The cycle and week a session was logged in, or the 4 week cycle counted from
+/// the week of the `first_unplanned` session when it wasn't logged from a plan
+fn cycle_and_week(session: &LoggedSession, first_unplanned: Option<NaiveDate>) -> (Cycle, u32) {
+    if let (Some(cycle), Some(week)) = (session.cycle, session.week) {
+        return (Cycle::Planned(cycle), week);
+    }
+    let monday = |date: NaiveDate| {
+        date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+    };
+    let first = monday(first_unplanned.unwrap_or(session.date));
+    let weeks = (monday(session.date) - first).num_days() / 7;
+    let start = first + chrono::Duration::weeks(weeks / 4 * 4);
+    (Cycle::Unplanned(start), (weeks % 4 + 1) as u32)
+}
+
+/// When none of the `cycles` up to and including the cycle at `idx` beat the best
+/// from the cycles before them, the best estimated one rep max and the cycle it's from
+fn stalled(bests: &IndexMap<Cycle, f32>, idx: usize, cycles: u32) -> Option<(Cycle, f32)> {
+    let since = idx.checked_sub(cycles as usize)?;
+    let mut before = None;
+    for (cycle, e1rm) in bests.iter().take(since + 1) {
+        if before.is_none_or(|(_, best)| *e1rm > best) {
+            before = Some((*cycle, *e1rm));
+        }
+    }
+    let (best_cycle, best) = before?;
+    let recent = bests
+        .values()
+        .skip(since + 1)
+        .take(idx - since)
+        .copied()
+        .reduce(f32::max)?;
+    if recent > best {
+        None
+    } else {
+        Some((best_cycle, best))
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Round to one decimal place for display
+fn round(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::LoggedSet;
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    /// A bench session with one work set, from a plan when `planned` has its cycle and week
+    fn session(day: &str, planned: Option<(u32, u32)>, weight: f32, reps: u8) -> LoggedSession {
+        LoggedSession {
+            date: date(day),
+            lift: Lift::Bench,
+            cycle: planned.map(|(cycle, _)| cycle),
+            week: planned.map(|(_, week)| week),
+            training_max: planned.map(|_| Weight::from(170.0)),
+            sets: vec![LoggedSet {
+                weight: weight.into(),
+                reps,
+                planned: None,
+                amrap: planned.is_some(),
+                warm_up: false,
+            }],
+            supplemental: Vec::new(),
+        }
+    }
+
+    /// A month and a bit of imported history followed by two cycles of a plan
+    fn log() -> Log {
+        Log {
+            sessions: vec![
+                session("2025-12-01", None, 200.0, 5),
+                session("2025-12-08", None, 205.0, 5),
+                session("2025-12-29", None, 210.0, 3),
+                session("2026-01-05", Some((1, 1)), 150.0, 8),
+                session("2026-01-12", Some((1, 2)), 160.0, 6),
+                session("2026-02-02", Some((2, 1)), 155.0, 8),
+            ],
+            bodyweights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn imported_sessions_are_kept_apart_from_plan_cycles() {
+        let stats = Stats::new(&log(), Period::Cycle, 2);
+        let cycles: Vec<Cycle> = stats.rows.iter().map(|row| row.cycle).collect();
+        assert_eq!(
+            cycles,
+            vec![
+                Cycle::Unplanned(date("2025-12-01")),
+                Cycle::Unplanned(date("2025-12-29")),
+                Cycle::Planned(1),
+                Cycle::Planned(2),
+            ]
+        );
+        let imported = &stats.rows[0];
+        assert_eq!(imported.sets, 2);
+        assert_eq!(imported.volume, 10);
+        assert_eq!(imported.training_max, None);
+    }
+
+    #[test]
+    fn plan_cycles_only_count_their_own_sessions() {
+        let stats = Stats::new(&log(), Period::Cycle, 2);
+        let first = &stats.rows[2];
+        assert_eq!(first.cycle, Cycle::Planned(1));
+        assert_eq!(first.date, date("2026-01-05"));
+        assert_eq!(first.training_max, Some(Weight::from(170.0)));
+        assert_eq!(first.e1rm, Some(round(one_rep_max(160.0, 6))));
+        assert_eq!(first.amrap_reps, 14);
+        assert_eq!(first.sets, 2);
+        assert_eq!(first.volume, 14);
+        assert_eq!(first.tonnage, Weight::from(150.0 * 8.0 + 160.0 * 6.0));
+    }
+
+    #[test]
+    fn imported_weeks_are_counted_from_the_first_imported_session() {
+        let stats = Stats::new(&log(), Period::Week, 2);
+        let weeks: Vec<(Cycle, Option<u32>)> =
+            stats.rows.iter().map(|row| (row.cycle, row.week)).collect();
+        assert_eq!(
+            weeks,
+            vec![
+                (Cycle::Unplanned(date("2025-12-01")), Some(1)),
+                (Cycle::Unplanned(date("2025-12-01")), Some(2)),
+                (Cycle::Unplanned(date("2025-12-29")), Some(1)),
+                (Cycle::Planned(1), Some(1)),
+                (Cycle::Planned(1), Some(2)),
+                (Cycle::Planned(2), Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_cycles_are_compared_with_the_imported_history_before_them() {
+        let stats = Stats::new(&log(), Period::Cycle, 2);
+        assert_eq!(stats.stalls.len(), 1);
+        let stall = &stats.stalls[0];
+        assert_eq!(stall.cycle, Cycle::Unplanned(date("2025-12-01")));
+        assert_eq!(stall.e1rm, round(one_rep_max(205.0, 5)));
+        let stalled: Vec<bool> = stats.rows.iter().map(|row| row.stalled).collect();
+        assert_eq!(stalled, vec![false, false, true, true]);
+    }
+}
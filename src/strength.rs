@@ -0,0 +1,196 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::weight::Weight;
+
+/// The coefficients for relative strength are different for men and women
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl FromStr for Sex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "male" | "m" => Self::Male,
+            "female" | "f" => Self::Female,
+            _ => return Err(format!("Unknown sex: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+/// A powerlifting total scored against bodyweight with each of the common formulas
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Points {
+    pub wilks: f32,
+    pub dots: f32,
+    pub ipf_gl: f32,
+}
+
+impl Points {
+    /// Score a `total` lifted at `bodyweight`, both in kg
+    pub fn new(total: Weight, bodyweight: Weight, sex: Sex) -> Self {
+        let (total, bodyweight) = (f32::from(total), f32::from(bodyweight));
+        Self {
+            wilks: wilks(total, bodyweight, sex),
+            dots: dots(total, bodyweight, sex),
+            ipf_gl: ipf_gl(total, bodyweight, sex),
+        }
+    }
+}
+
+/// The original Wilks formula, with the bodyweight limited to the range it was fit to
+pub fn wilks(total: f32, bodyweight: f32, sex: Sex) -> f32 {
+    let (coefficients, min, max) = match sex {
+        Sex::Male => (
+            [
+                -216.0475144,
+                16.2606339,
+                -0.002388645,
+                -0.00113732,
+                7.01863e-6,
+                -1.291e-8,
+            ],
+            40.0,
+            201.9,
+        ),
+        Sex::Female => (
+            [
+                594.31747775582,
+                -27.23842536447,
+                0.82112226871,
+                -0.00930733913,
+                4.731582e-5,
+                -9.054e-8,
+            ],
+            26.51,
+            154.53,
+        ),
+    };
+    total * 500.0 / polynomial(&coefficients, bodyweight.clamp(min, max))
+}
+
+/// DOTS, with the bodyweight limited to the range it was fit to
+pub fn dots(total: f32, bodyweight: f32, sex: Sex) -> f32 {
+    let (coefficients, max) = match sex {
+        Sex::Male => (
+            [
+                -307.75076,
+                24.0900756,
+                -0.1918759221,
+                0.0007391293,
+                -0.000001093,
+            ],
+            210.0,
+        ),
+        Sex::Female => (
+            [
+                -57.96288,
+                13.6175032,
+                -0.1126655495,
+                0.0005158568,
+                -0.0000010706,
+            ],
+            150.0,
+        ),
+    };
+    total * 500.0 / polynomial(&coefficients, bodyweight.clamp(40.0, max))
+}
+
+/// IPF GL points for a classic (raw) powerlifting total
+pub fn ipf_gl(total: f32, bodyweight: f32, sex: Sex) -> f32 {
+    let (a, b, c) = match sex {
+        Sex::Male => (1199.72839, 1025.18162, 0.00921),
+        Sex::Female => (610.32796, 1045.59282, 0.03048),
+    };
+    let denominator = a - b * (-c * bodyweight as f64).exp();
+    (total as f64 * 100.0 / denominator) as f32
+}
+
+/// `coefficients[0] + coefficients[1] * x + coefficients[2] * x^2 + ...`, worked out in f64
+fn polynomial(coefficients: &[f64], x: f32) -> f32 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |sum, coefficient| sum * x as f64 + coefficient) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A total in kg, the lifter's bodyweight in kg and their Wilks, DOTS and IPF GL scores,
+    /// worked out from the published formulas to two decimal places
+    const REFERENCES: &[(f32, f32, Sex, [f32; 3])] = &[
+        (500.0, 90.0, Sex::Male, [319.20, 323.30, 66.47]),
+        (700.0, 100.0, Sex::Male, [426.01, 430.86, 88.43]),
+        (350.0, 60.0, Sex::Female, [390.21, 387.99, 79.11]),
+        (400.0, 75.0, Sex::Female, [380.26, 389.59, 79.36]),
+    ];
+
+    fn assert_close(actual: f32, expected: f32, formula: &str, bodyweight: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{} at {}kg was {}, expected {}",
+            formula,
+            bodyweight,
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn scores_match_the_references() {
+        for &(total, bodyweight, sex, [wilks, dots, ipf_gl]) in REFERENCES {
+            let points = Points::new(total.into(), bodyweight.into(), sex);
+            assert_close(points.wilks, wilks, "Wilks", bodyweight);
+            assert_close(points.dots, dots, "DOTS", bodyweight);
+            assert_close(points.ipf_gl, ipf_gl, "IPF GL", bodyweight);
+        }
+    }
+
+    #[test]
+    fn bodyweights_outside_the_fitted_range_are_limited() {
+        assert_eq!(
+            wilks(800.0, 250.0, Sex::Male),
+            wilks(800.0, 201.9, Sex::Male)
+        );
+        assert_eq!(
+            wilks(200.0, 20.0, Sex::Female),
+            wilks(200.0, 26.51, Sex::Female)
+        );
+        assert_eq!(dots(800.0, 250.0, Sex::Male), dots(800.0, 210.0, Sex::Male));
+        assert_eq!(
+            dots(300.0, 170.0, Sex::Female),
+            dots(300.0, 150.0, Sex::Female)
+        );
+        assert_eq!(
+            dots(200.0, 30.0, Sex::Female),
+            dots(200.0, 40.0, Sex::Female)
+        );
+    }
+
+    #[test]
+    fn scores_go_up_with_the_total() {
+        for &sex in &[Sex::Male, Sex::Female] {
+            let lighter = Points::new(Weight::from(400.0), Weight::from(80.0), sex);
+            let heavier = Points::new(Weight::from(410.0), Weight::from(80.0), sex);
+            assert!(heavier.wilks > lighter.wilks);
+            assert!(heavier.dots > lighter.dots);
+            assert!(heavier.ipf_gl > lighter.ipf_gl);
+        }
+    }
+
+    #[test]
+    fn parses_sex() {
+        assert_eq!("M".parse(), Ok(Sex::Male));
+        assert_eq!("female".parse(), Ok(Sex::Female));
+        assert!("x".parse::<Sex>().is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::NaiveDate;
+
+use crate::{
+    log::Log,
+    plan::{one_rep_max, Lift},
+    weight::Weight,
+};
+
+/// A training max worked out from a recent top set
+#[derive(Debug, Clone, Copy)]
+pub struct Suggestion {
+    pub lift: Lift,
+    pub weight: Weight,
+    pub reps: u8,
+    /// The day the set was done, when it came from a log
+    pub date: Option<NaiveDate>,
+    /// The percent of the estimated one rep max the training max is
+    pub percent: f32,
+    /// What the training max is rounded up to a multiple of
+    pub rounding: Weight,
+}
+
+impl Suggestion {
+    pub fn new(lift: Lift, weight: Weight, reps: u8, percent: f32, rounding: Weight) -> Self {
+        Self {
+            lift,
+            weight,
+            reps,
+            date: None,
+            percent,
+            rounding,
+        }
+    }
+
+    /// From the set for `lift` in the `log` with the highest estimated one rep max, on `since` or later
+    pub fn from_log(
+        log: &Log,
+        lift: Lift,
+        since: NaiveDate,
+        percent: f32,
+        rounding: Weight,
+    ) -> Option<Self> {
+        let (date, set) = log.best_set(lift, since)?;
+        Some(Self {
+            date: Some(date),
+            ..Self::new(lift, set.weight, set.reps, percent, rounding)
+        })
+    }
+
+    pub fn one_rep_max(&self) -> f32 {
+        one_rep_max(self.weight.into(), self.reps)
+    }
+
+    pub fn training_max(&self) -> Weight {
+        Weight::from(self.one_rep_max() * self.percent / 100.0).round_up_to(self.rounding)
+    }
+
+    /// Each step from the set to the training max, for example
+    /// `225 x5 is an estimated 1RM of 225 x 5 x 0.0333 + 225 = 262.5, ...`
+    pub fn explain(&self) -> String {
+        let mut text = format!("{} x{}", self.weight, self.reps);
+        if let Some(date) = self.date {
+            text.push_str(&format!(" on {}", date));
+        }
+        text.push_str(&format!(
+            " is an estimated 1RM of {w} x {r} x 0.0333 + {w} = {e1rm}, {p}% of that is {tm}, rounded up to {rounded}",
+            w = self.weight,
+            r = self.reps,
+            e1rm = tenths(self.one_rep_max()),
+            p = self.percent,
+            tm = tenths(self.one_rep_max() * self.percent / 100.0),
+            rounded = self.training_max(),
+        ));
+        text
+    }
+}
+
+fn tenths(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
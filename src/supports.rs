@@ -0,0 +1,692 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    fmt,
+    path::Path,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    file,
+    implements::Implement,
+    plan::{percent_of, Lift, Week},
+    weight::Weight,
+};
+
+/// The supporting exercises for each lift's day
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Supports {
+    /// The weeks of each cycle to include supporting work on, for the exercises
+    /// without their own `weeks`, every week when it isn't provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub included_weeks: Option<Vec<u8>>,
+    /// The weeks of each cycle to include supporting work on for one lift, instead of `included_weeks`
+    #[serde(default)]
+    pub weeks: BTreeMap<Lift, Vec<u8>>,
+    #[serde(default)]
+    pub bench: Vec<Support>,
+    #[serde(default)]
+    pub dead: Vec<Support>,
+    #[serde(default)]
+    pub ohp: Vec<Support>,
+    #[serde(default)]
+    pub squat: Vec<Support>,
+    /// The dumbbells, kettlebells and other implements used for supporting work
+    #[serde(default)]
+    pub implements: Vec<Implement>,
+    /// The reps of each category to do on a day, categories without one use `DEFAULT_TARGET`
+    #[serde(default)]
+    pub targets: BTreeMap<Category, RepRange>,
+    /// Named lists of exercises that a lift can include with `preset = "<name>"`
+    #[serde(default)]
+    pub presets: BTreeMap<String, Vec<Support>>,
+    /// Jumps and throws done before the main lift, on the days of the lifts in their `lifts`
+    #[serde(default)]
+    pub jumps: Vec<Support>,
+    /// Conditioning done after the supporting work, on the days of the lifts in their `lifts`
+    #[serde(default)]
+    pub conditioning: Vec<Support>,
+    /// How many days of hard and easy conditioning each week should have
+    #[serde(default)]
+    pub conditioning_days: ConditioningDays,
+    /// The athlete's bodyweight for loads relative to it, this isn't read from the file
+    #[serde(skip)]
+    pub bodyweight: Option<Weight>,
+}
+
+/// The number of days of each intensity of conditioning to do in a week, unchecked when not provided
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct ConditioningDays {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard: Option<RepRange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub easy: Option<RepRange>,
+}
+
+/// 5/3/1 Forever's suggestion for each category of assistance on a day
+pub const DEFAULT_TARGET: RepRange = RepRange { min: 50, max: 100 };
+
+impl Supports {
+    /// Read the supporting exercises from a .toml, .json or .yaml file, with any
+    /// presets expanded and each weight moved to one its implement can be set to
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let mut supports: Self = file::read(path)?;
+        supports.expand_presets()?;
+        supports.snap_weights()?;
+        Ok(supports)
+    }
+
+    pub fn with_bodyweight(mut self, bodyweight: Option<Weight>) -> Self {
+        self.bodyweight = bodyweight;
+        self
+    }
+
+    /// Every supporting exercise listed for `lift`, whichever weeks it's done in
+    pub fn for_lift(&self, lift: Lift) -> &[Support] {
+        match lift {
+            Lift::Bench => &self.bench,
+            Lift::Dead => &self.dead,
+            Lift::Ohp => &self.ohp,
+            Lift::Squat => &self.squat,
+        }
+    }
+
+    /// The supporting exercises for `lift` in the `week`, with the weight
+    /// of any that are a percent of a training max worked out
+    pub fn for_day(&self, lift: Lift, week: &Week) -> Vec<Support> {
+        self.included(lift, week)
+            .map(|support| self.resolve(support, lift, week))
+            .collect()
+    }
+
+    /// The jumps and throws done before `lift` in the `week`
+    pub fn jumps_for_day(&self, lift: Lift, week: &Week) -> Vec<Support> {
+        scheduled(&self.jumps, lift, week, None)
+            .map(|support| self.resolve(support, lift, week))
+            .collect()
+    }
+
+    /// The conditioning done after `lift` in the `week`
+    pub fn conditioning_for_day(&self, lift: Lift, week: &Week) -> Vec<Support> {
+        scheduled(&self.conditioning, lift, week, None)
+            .map(|support| self.resolve(support, lift, week))
+            .collect()
+    }
+
+    /// The number of hard and easy conditioning days in the `week`, a day
+    /// with both counts as hard
+    pub fn conditioning_days(&self, week: &Week) -> (u32, u32) {
+        let mut days = (0, 0);
+        for day in &week.days {
+            let intensities: Vec<_> = scheduled(&self.conditioning, day.lift, week, None)
+                .filter_map(|support| support.intensity)
+                .collect();
+            if intensities.contains(&Intensity::Hard) {
+                days.0 += 1;
+            } else if intensities.contains(&Intensity::Easy) {
+                days.1 += 1;
+            }
+        }
+        days
+    }
+
+    /// A copy of `support` done on `day`'s lift with the weight of a percent of a training max or
+    /// of bodyweight worked out, rounded like the lift it's a percent of or the day's lift for
+    /// bodyweight, or moved to the closest weight its implement can be set to when it has one
+    fn resolve(&self, support: &Support, day: Lift, week: &Week) -> Support {
+        let mut support = support.clone();
+        let implement = support
+            .implement
+            .as_deref()
+            .and_then(|name| self.implements.iter().find(|i| i.name == name));
+        let achievable = |base: Weight, percent: f32, rounding: Weight| match implement {
+            Some(implement) => {
+                let weight = base.times(percent / 100.0);
+                implement.closest_weight(weight).unwrap_or(weight)
+            }
+            None => percent_of(base, percent / 100.0, rounding),
+        };
+        match support.load {
+            Some(Load::Percent { lift, percent }) => {
+                support.weight = Some(achievable(
+                    week.training_max.get(lift),
+                    percent,
+                    week.rounding.get(lift),
+                ));
+            }
+            Some(Load::Relative(percent)) => {
+                // assistance is worked out the same way as added weight, then made negative
+                support.weight = self.bodyweight.map(|bodyweight| {
+                    let weight = achievable(bodyweight, percent.abs(), week.rounding.get(day));
+                    if percent < 0.0 {
+                        -weight
+                    } else {
+                        weight
+                    }
+                });
+            }
+            _ => {}
+        }
+        support
+    }
+
+    /// The exercises listed for `lift` that are scheduled for its day in the `week`
+    fn included<'a>(&'a self, lift: Lift, week: &Week) -> impl Iterator<Item = &'a Support> {
+        let weeks = self.weeks.get(&lift).or(self.included_weeks.as_ref());
+        scheduled(self.for_lift(lift), lift, week, weeks)
+    }
+
+    /// The rotation group done on `lift`'s day in the `week`, the groups take
+    /// turns in the order of their names with every week of every cycle
+    /// counting as a turn, even when it has no supporting work
+    pub fn group(&self, lift: Lift, week: &Week) -> Option<&str> {
+        group(self.for_lift(lift), lift, week)
+    }
+
+    /// The reps of assistance for each category on `lift`'s day in the `week`,
+    /// exercises without both sets and reps aren't counted
+    pub fn volume(&self, lift: Lift, week: &Week) -> BTreeMap<Category, RepRange> {
+        let mut volume: BTreeMap<Category, RepRange> = BTreeMap::new();
+        for support in self.included(lift, week) {
+            if let (Some(category), Some(reps)) = (support.category, support.total_reps()) {
+                let total = volume.entry(category).or_default();
+                total.min = total.min.saturating_add(reps.min);
+                total.max = total.max.saturating_add(reps.max);
+            }
+        }
+        volume
+    }
+
+    /// The reps `category` should have on a day
+    pub fn target(&self, category: Category) -> RepRange {
+        self.targets
+            .get(&category)
+            .copied()
+            .unwrap_or(DEFAULT_TARGET)
+    }
+
+    /// Replace each exercise that names a preset with the exercises in that preset, which
+    /// are done in the same weeks, cycles, group, lifts and intensity unless they have their own
+    pub fn expand_presets(&mut self) -> Result<()> {
+        let mut problems = Vec::new();
+        let presets = &self.presets;
+        for (label, list) in [
+            (Lift::Bench.name(), &mut self.bench),
+            (Lift::Dead.name(), &mut self.dead),
+            (Lift::Ohp.name(), &mut self.ohp),
+            (Lift::Squat.name(), &mut self.squat),
+            ("jumps", &mut self.jumps),
+            ("conditioning", &mut self.conditioning),
+        ] {
+            let mut expanded = Vec::with_capacity(list.len());
+            for support in list.drain(..) {
+                let name = match &support.preset {
+                    Some(name) => name,
+                    None if support.name.is_empty() => {
+                        problems.push(format!("Each {} exercise needs a name or a preset", label));
+                        continue;
+                    }
+                    None => {
+                        expanded.push(support);
+                        continue;
+                    }
+                };
+                let exercises = match presets.get(name) {
+                    Some(exercises) => exercises,
+                    None => {
+                        problems.push(format!("Unknown preset {:?} for {}", name, label));
+                        continue;
+                    }
+                };
+                for exercise in exercises {
+                    if exercise.preset.is_some() {
+                        problems.push(format!(
+                            "The preset {:?} includes another preset, which isn't supported",
+                            name
+                        ));
+                        break;
+                    }
+                    let mut exercise = exercise.clone();
+                    exercise.weeks = exercise.weeks.or_else(|| support.weeks.clone());
+                    exercise.cycles = exercise.cycles.or_else(|| support.cycles.clone());
+                    exercise.group = exercise.group.or_else(|| support.group.clone());
+                    exercise.lifts = exercise.lifts.or_else(|| support.lifts.clone());
+                    exercise.intensity = exercise.intensity.or(support.intensity);
+                    expanded.push(exercise);
+                }
+            }
+            *list = expanded;
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(problems))
+        }
+    }
+
+    /// Move each supporting exercise's weight to the closest weight its
+    /// implement can actually be set to
+    pub fn snap_weights(&mut self) -> Result<()> {
+        let mut unknown = Vec::new();
+        let implements = &self.implements;
+        for support in self
+            .bench
+            .iter_mut()
+            .chain(self.dead.iter_mut())
+            .chain(self.ohp.iter_mut())
+            .chain(self.squat.iter_mut())
+            .chain(self.jumps.iter_mut())
+            .chain(self.conditioning.iter_mut())
+        {
+            if let Some(Load::Fixed(weight)) = support.load {
+                support.weight = Some(weight);
+            }
+            let (name, weight) = match (&support.implement, support.weight) {
+                (Some(name), Some(weight)) => (name, weight),
+                _ => continue,
+            };
+            if let Some(implement) = implements.iter().find(|i| &i.name == name) {
+                support.weight = implement.closest_weight(weight);
+            } else {
+                unknown.push(format!(
+                    "Unknown implement {:?} for {:?}",
+                    name, support.name
+                ));
+            }
+        }
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(unknown))
+        }
+    }
+}
+
+/// The `exercises` scheduled for `lift`'s day in the `week`, `weeks` are the
+/// weeks for the exercises that don't have their own
+fn scheduled<'a>(
+    exercises: &'a [Support],
+    lift: Lift,
+    week: &Week,
+    weeks: Option<&'a Vec<u8>>,
+) -> impl Iterator<Item = &'a Support> {
+    let group = group(exercises, lift, week);
+    let (cycle, number) = (week.cycle, week.number);
+    exercises.iter().filter(move |support| {
+        support.is_for(lift)
+            && support
+                .weeks
+                .as_ref()
+                .or(weeks)
+                .is_none_or(|weeks| weeks.iter().any(|included| *included as u32 == number))
+            && support
+                .cycles
+                .as_ref()
+                .is_none_or(|cycles| cycles.contains(&cycle))
+            && support
+                .group
+                .as_deref()
+                .is_none_or(|name| Some(name) == group)
+    })
+}
+
+/// The rotation group of the `exercises` for `lift` done in the `week`
+fn group<'a>(exercises: &'a [Support], lift: Lift, week: &Week) -> Option<&'a str> {
+    let groups: BTreeSet<&str> = exercises
+        .iter()
+        .filter(|support| support.is_for(lift))
+        .filter_map(|support| support.group.as_deref())
+        .collect();
+    if groups.is_empty() {
+        return None;
+    }
+    let turn = (week.cycle.saturating_sub(1) * 4 + week.number.saturating_sub(1)) as usize;
+    let count = groups.len();
+    groups.into_iter().nth(turn % count)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Support {
+    /// Only empty when the exercise is a `preset`
+    #[serde(default)]
+    pub name: String,
+    /// The name of a list of exercises from `Supports::presets` to do instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// The name of the implement from `Supports::implements` used for this exercise
+    #[serde(default)]
+    pub implement: Option<String>,
+    /// The suggested weight for this exercise, the same as a fixed `load`
+    #[serde(default)]
+    pub weight: Option<Weight>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<Category>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sets: Option<u8>,
+    /// The reps for each set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reps: Option<RepRange>,
+    /// How the weight is worked out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<Load>,
+    /// The weeks of each cycle this exercise is done in, instead of the ones for its lift
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weeks: Option<Vec<u8>>,
+    /// The cycles this exercise is done in, every cycle when it isn't provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycles: Option<Vec<u32>>,
+    /// The rotation group this exercise is in, the groups for a lift take turns each week
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// The lifts whose days jumps, throws and conditioning are done on, every day when it isn't provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifts: Option<Vec<Lift>>,
+    /// How hard a piece of conditioning is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intensity: Option<Intensity>,
+    /// Work that isn't sets of reps, for example `10 x 40 yd` or `20 min`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl Support {
+    fn is_for(&self, lift: Lift) -> bool {
+        self.lifts
+            .as_ref()
+            .is_none_or(|lifts| lifts.contains(&lift))
+    }
+
+    /// The reps across every set, when both the sets and reps are known
+    pub fn total_reps(&self) -> Option<RepRange> {
+        let sets = self.sets?;
+        let reps = self.reps?;
+        Some(RepRange {
+            min: reps.min.saturating_mul(sets as u16),
+            max: reps.max.saturating_mul(sets as u16),
+        })
+    }
+
+    /// The sets, reps, load and notes, for example `5x10-15 @ 45` or `3x8 @ BW`
+    pub fn prescription(&self) -> String {
+        let mut text = match (self.sets_reps(), self.load_text()) {
+            (sets_reps, Some(load)) if sets_reps.is_empty() => format!("@ {}", load),
+            (sets_reps, Some(load)) => format!("{} @ {}", sets_reps, load),
+            (sets_reps, None) => sets_reps,
+        };
+        if let Some(notes) = &self.notes {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(notes);
+        }
+        text
+    }
+
+    /// The name and prescription, with the intensity of conditioning,
+    /// for example `Hill Sprints 10 x 40 yd (hard)`
+    pub fn describe(&self) -> String {
+        let mut text = self.name.clone();
+        let prescription = self.prescription();
+        if !prescription.is_empty() {
+            text.push_str(&format!(" {}", prescription));
+        }
+        if let Some(intensity) = self.intensity {
+            text.push_str(&format!(" ({})", intensity.name()));
+        }
+        text
+    }
+
+    /// The weight on the bar for supporting work that is a percent of a training max
+    /// without an implement, like close grip bench or boring but big sets
+    pub fn bar_weight(&self) -> Option<Weight> {
+        match (self.load, &self.implement, self.weight) {
+            (Some(Load::Percent { .. }), None, Some(weight)) => Some(weight),
+            _ => None,
+        }
+    }
+
+    /// The sets and reps, for example `5x10-15`, empty when neither are provided
+    pub fn sets_reps(&self) -> String {
+        match (self.sets, self.reps) {
+            (Some(sets), Some(reps)) => format!("{}x{}", sets, reps),
+            (Some(sets), None) => format!("{} sets", sets),
+            (None, Some(reps)) => format!("{} reps", reps),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// The weight to use, or `BW` for a bodyweight exercise with any weight added or
+    /// taken off, for example `BW+25`, or `BW+10%` when the bodyweight isn't known
+    pub fn load_text(&self) -> Option<String> {
+        match (self.load, self.weight) {
+            (Some(Load::Bodyweight), _) => Some("BW".to_string()),
+            (Some(Load::Relative(_)), Some(weight)) if weight < Weight::ZERO => {
+                Some(format!("BW{}", weight))
+            }
+            (Some(Load::Relative(_)), Some(weight)) => Some(format!("BW+{}", weight)),
+            (Some(Load::Relative(percent)), None) => Some(format!("BW{:+}%", percent)),
+            (_, Some(weight)) => Some(weight.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// How hard a piece of conditioning is, 5/3/1 Forever balances a few hard days with easy ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Intensity {
+    Hard,
+    Easy,
+}
+
+impl Intensity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hard => "hard",
+            Self::Easy => "easy",
+        }
+    }
+}
+
+/// The kinds of assistance in 5/3/1 Forever, each with its own volume target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Category {
+    Push,
+    Pull,
+    /// Single leg or core work
+    Core,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [Category::Push, Category::Pull, Category::Core];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Push => "push",
+            Self::Pull => "pull",
+            Self::Core => "single leg/core",
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let ret = match s.to_lowercase().as_str() {
+            "push" => Self::Push,
+            "pull" => Self::Pull,
+            "core" | "single-leg" | "single leg" | "single-leg/core" | "single leg/core" => {
+                Self::Core
+            }
+            _ => return Err(format!("Unknown assistance category: {:?}", s)),
+        };
+        Ok(ret)
+    }
+}
+
+impl TryFrom<String> for Category {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Category> for String {
+    /// The name `FromStr` parses, so categories can be used as map keys
+    fn from(category: Category) -> Self {
+        match category {
+            Category::Push => "push",
+            Category::Pull => "pull",
+            Category::Core => "core",
+        }
+        .to_string()
+    }
+}
+
+/// A number of reps, or a range like `10-20`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(try_from = "RawNumber", into = "String")]
+pub struct RepRange {
+    pub min: u16,
+    pub max: u16,
+}
+
+impl fmt::Display for RepRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "{}-{}", self.min, self.max)
+        }
+    }
+}
+
+impl FromStr for RepRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid reps {:?}, expected a number or a range like 10-20",
+                s
+            )
+        };
+        let (min, max) = match s.split_once('-') {
+            Some((min, max)) => (min, max),
+            None => (s, s),
+        };
+        let min: u16 = min.trim().parse().map_err(|_| invalid())?;
+        let max: u16 = max.trim().parse().map_err(|_| invalid())?;
+        if min == 0 || min > max {
+            return Err(invalid());
+        }
+        Ok(Self { min, max })
+    }
+}
+
+impl TryFrom<RawNumber> for RepRange {
+    type Error = String;
+
+    fn try_from(raw: RawNumber) -> std::result::Result<Self, Self::Error> {
+        match raw {
+            RawNumber::Number(reps) => reps.to_string().parse(),
+            RawNumber::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<RepRange> for String {
+    fn from(range: RepRange) -> Self {
+        range.to_string()
+    }
+}
+
+/// How the weight of a supporting exercise is worked out
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawNumber", into = "String")]
+pub enum Load {
+    /// No added weight, written `bodyweight` or `bw`
+    Bodyweight,
+    /// A percent of bodyweight added, or taken off with assistance when it's negative,
+    /// written like `bw+10%` or `bodyweight - 20%`
+    Relative(f32),
+    /// A percent of a main lift's training max, written like `50% ohp`
+    Percent { lift: Lift, percent: f32 },
+    /// The same weight every week, written as a number
+    Fixed(Weight),
+}
+
+impl FromStr for Load {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("bodyweight") || s.eq_ignore_ascii_case("bw") {
+            return Ok(Self::Bodyweight);
+        }
+        if let Ok(weight) = s.parse::<Weight>() {
+            return Ok(Self::Fixed(weight));
+        }
+        let invalid = || {
+            format!(
+                "Invalid load {:?}, expected bodyweight, a weight or a percent of a lift like 50% ohp",
+                s
+            )
+        };
+        let lower = s.to_lowercase();
+        if let Some(relative) = lower
+            .strip_prefix("bodyweight")
+            .or_else(|| lower.strip_prefix("bw"))
+        {
+            let relative = relative.replace(' ', "");
+            let percent = relative.strip_suffix('%').ok_or_else(invalid)?;
+            if !percent.starts_with(['+', '-']) {
+                return Err(invalid());
+            }
+            return percent.parse().map(Self::Relative).map_err(|_| invalid());
+        }
+        let (percent, lift) = s.split_once('%').ok_or_else(invalid)?;
+        let lift = lift.trim();
+        let lift = lift.strip_prefix("of ").unwrap_or(lift);
+        Ok(Self::Percent {
+            lift: lift.parse()?,
+            percent: percent.trim().parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl TryFrom<RawNumber> for Load {
+    type Error = String;
+
+    fn try_from(raw: RawNumber) -> std::result::Result<Self, Self::Error> {
+        match raw {
+            RawNumber::Number(weight) => weight.to_string().parse().map(Self::Fixed),
+            RawNumber::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<Load> for String {
+    fn from(load: Load) -> Self {
+        match load {
+            Load::Bodyweight => "bodyweight".to_string(),
+            Load::Relative(percent) => format!("bodyweight{:+}%", percent),
+            Load::Percent { lift, percent } => format!("{}% {}", percent, String::from(lift)),
+            Load::Fixed(weight) => weight.to_string(),
+        }
+    }
+}
+
+/// A value that can be written as either a number or a string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawNumber {
+    Number(f64),
+    Text(String),
+}
@@ -0,0 +1,211 @@
+use crossterm::style::Stylize;
+
+use crate::{
+    loading::LoadingPlanner,
+    plan::{Program, Week},
+    render::{render_day, RenderOptions},
+    supports::Supports,
+};
+
+/// How many weeks are printed side by side when the width of the terminal isn't known
+pub const DEFAULT_COLUMNS: usize = 4;
+
+/// How a line of plain text should be highlighted when colours are used
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Plain,
+    /// The name of a lift
+    Title,
+    Amrap,
+}
+
+/// One line of a week's column
+#[derive(Debug, Clone)]
+struct Line {
+    text: String,
+    style: Style,
+}
+
+impl Line {
+    fn new(text: impl Into<String>, style: Style) -> Self {
+        Self {
+            text: text.into(),
+            style,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn paint(&self, color: bool) -> String {
+        if !color {
+            return self.text.clone();
+        }
+        match self.style {
+            Style::Plain => self.text.clone(),
+            Style::Title => self.text.as_str().bold().to_string(),
+            Style::Amrap => self.text.as_str().yellow().bold().to_string(),
+        }
+    }
+}
+
+/// Render a program as plain text, with each week in a column. The number of columns
+/// is `options.columns`, or as many as fit in `options.width`, or `DEFAULT_COLUMNS`
+pub fn render(
+    program: &Program,
+    supports: &Supports,
+    planner: &LoadingPlanner,
+    options: RenderOptions,
+) -> String {
+    let weeks: Vec<Vec<Vec<Line>>> = program
+        .weeks
+        .iter()
+        .map(|week| render_week(week, supports, planner, options))
+        .collect();
+    let longest_line = weeks
+        .iter()
+        .flatten()
+        .flatten()
+        .map(Line::width)
+        .max()
+        .unwrap_or_default();
+    let columns = options
+        .columns
+        .unwrap_or_else(|| match options.width {
+            Some(width) => width / longest_line.max(1),
+            None => DEFAULT_COLUMNS,
+        })
+        .max(1);
+    let empty = Line::new("", Style::Plain);
+    let mut out = String::new();
+    for row in weeks.chunks(columns) {
+        let sections = row.iter().map(Vec::len).max().unwrap_or_default();
+        for section in 0..sections {
+            // each section is as tall as the longest in the row, so the lifts line up
+            let height = row
+                .iter()
+                .filter_map(|week| week.get(section))
+                .map(Vec::len)
+                .max()
+                .unwrap_or_default();
+            for idx in 0..height {
+                let mut text = String::new();
+                for (column, week) in row.iter().enumerate() {
+                    let line = week
+                        .get(section)
+                        .and_then(|lines| lines.get(idx))
+                        .unwrap_or(&empty);
+                    text.push_str(&line.paint(options.color));
+                    if column + 1 < row.len() {
+                        text.push_str(&" ".repeat(longest_line.saturating_sub(line.width())));
+                    }
+                }
+                out.push_str(text.trim_end());
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// The lines for one week's column, split into the title and then
+/// a section for each lift with its sets and supporting work
+fn render_week(
+    week: &Week,
+    supports: &Supports,
+    planner: &LoadingPlanner,
+    options: RenderOptions,
+) -> Vec<Vec<Line>> {
+    let mut title = vec![
+        Line::new(SEPARATOR, Style::Plain),
+        Line::new(week_title(week), Style::Plain),
+    ];
+    if !supports.conditioning.is_empty() {
+        title.push(Line::new(conditioning_days(supports, week), Style::Plain));
+    }
+    title.push(Line::new(SEPARATOR, Style::Plain));
+    let mut sections = vec![title];
+    for day in &week.days {
+        let day_supports = supports.for_day(day.lift, week);
+        let rendered = render_day(day, &day_supports, planner, options);
+        let mut lines = Vec::new();
+        lines.push(Line::new(day.lift.name(), Style::Title));
+        for jump in supports.jumps_for_day(day.lift, week) {
+            lines.push(Line::new(format!(" > {}", jump.describe()), Style::Plain));
+        }
+        for (set, planned) in rendered.warm_up.iter().zip(&day.warm_up) {
+            let mut text = format!(" {}{} x{} warm up", set.value, set.side, planned.reps);
+            if let Some(change) = &set.change {
+                text.push_str(&format!(" {}", change));
+            }
+            lines.push(Line::new(text, Style::Plain));
+        }
+        for (set, planned) in rendered.work.iter().zip(&day.work) {
+            let mut text = format!(" {}{}", set.value, set.side);
+            if let Some(target) = set.target {
+                text.push_str(&format!(" {} for a PR", target));
+            }
+            if let Some(change) = &set.change {
+                text.push_str(&format!(" {}", change));
+            }
+            let style = if planned.amrap {
+                Style::Amrap
+            } else {
+                Style::Plain
+            };
+            lines.push(Line::new(text, style));
+        }
+        for (support, plates) in day_supports.iter().zip(&rendered.supports) {
+            let mut text = format!(" + {}", support.describe());
+            if let Some(side) = &plates.side {
+                text.push_str(&format!(" {}", side));
+            }
+            if let Some(change) = &plates.change {
+                text.push_str(&format!(" {}", change));
+            }
+            lines.push(Line::new(text, Style::Plain));
+        }
+        let volume = supports.volume(day.lift, week);
+        if !volume.is_empty() {
+            let totals: Vec<String> = volume
+                .iter()
+                .map(|(category, reps)| format!("{} {}", reps, category.name()))
+                .collect();
+            lines.push(Line::new(
+                format!(" = {} reps", totals.join(", ")),
+                Style::Plain,
+            ));
+        }
+        for conditioning in supports.conditioning_for_day(day.lift, week) {
+            lines.push(Line::new(
+                format!(" * {}", conditioning.describe()),
+                Style::Plain,
+            ));
+        }
+        sections.push(lines);
+    }
+    sections
+}
+
+/// The number of conditioning days in a week, for example `Conditioning 2 hard, 2 easy`
+pub fn conditioning_days(supports: &Supports, week: &Week) -> String {
+    let (hard, easy) = supports.conditioning_days(week);
+    format!("Conditioning {} hard, {} easy", hard, easy)
+}
+
+const SEPARATOR: &str = "--------------------------";
+
+/// The week number and the reps for its work sets, for example `Week 3: Reps 5/3/1`
+pub fn week_title(week: &Week) -> String {
+    if week.number == 3 {
+        return format!("Week {}: Reps 5/3/1", week.number);
+    }
+    let reps = week
+        .days
+        .first()
+        .and_then(|day| day.work.first())
+        .map(|set| set.reps)
+        .unwrap_or_default();
+    format!("Week {}: Reps {}", week.number, reps)
+}
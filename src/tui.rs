@@ -0,0 +1,351 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    error::{Error, Result},
+    file::{self, Format},
+    loading::LoadingPlanner,
+    plan::{Lift, PerLift, Program, ProgramBuilder, TrainingMax},
+    render::{self, render_day, RenderOptions},
+    supports::Supports,
+    terminal::{self as text, week_title},
+    weight::Weight,
+};
+
+/// An interactive view of a plan that pages through the weeks one at a time,
+/// rebuilding the plan as the training maxes and rounding are adjusted
+pub struct Tui {
+    builder: ProgramBuilder,
+    training_max: TrainingMax,
+    rounding: PerLift<Weight>,
+    planner: LoadingPlanner,
+    supports: Supports,
+    options: RenderOptions,
+    program: Program,
+    /// The index of the week being shown
+    week: usize,
+    /// The index into `Lift::ALL` of the selected day
+    lift: usize,
+    /// The path being typed while exporting
+    export: Option<String>,
+    /// The outcome of the last export
+    status: Option<String>,
+}
+
+/// What a key press asks the view to do next
+enum Action {
+    Continue,
+    Quit,
+}
+
+impl Tui {
+    /// The `builder`'s training maxes and rounding are replaced by the
+    /// ones provided here, which are the values that can be adjusted
+    pub fn new(
+        builder: ProgramBuilder,
+        training_max: TrainingMax,
+        rounding: PerLift<Weight>,
+        planner: LoadingPlanner,
+    ) -> Self {
+        let mut tui = Self {
+            builder,
+            training_max,
+            rounding,
+            planner,
+            supports: Supports::default(),
+            options: RenderOptions::default(),
+            program: Program { weeks: Vec::new() },
+            week: 0,
+            lift: 0,
+            export: None,
+            status: None,
+        };
+        tui.rebuild();
+        tui
+    }
+
+    /// The extra exercises included when exporting to html
+    pub fn with_supports(mut self, supports: Supports) -> Self {
+        self.supports = supports;
+        self
+    }
+
+    /// The options used when exporting, the plates and changes are always shown in the view
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Take over the terminal until the view is closed
+    pub fn run(mut self) -> Result<()> {
+        let mut out = io::stdout();
+        let _screen = Screen::enter(&mut out).map_err(Error::Terminal)?;
+        loop {
+            self.draw(&mut out).map_err(Error::Terminal)?;
+            let key = match event::read().map_err(Error::Terminal)? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => key,
+                _ => continue,
+            };
+            if let Action::Quit = self.handle(key) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let mut builder = self.builder.clone().training_max(self.training_max);
+        for &lift in &Lift::ALL {
+            builder = builder.rounding(lift, self.rounding.get(lift));
+        }
+        self.program = builder.build();
+        self.week = self.week.min(self.program.weeks.len().saturating_sub(1));
+    }
+
+    fn handle(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        if let Some(path) = &mut self.export {
+            match key.code {
+                KeyCode::Char(c) => path.push(c),
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Enter => {
+                    let path = path.trim().to_string();
+                    self.export = None;
+                    self.status = Some(match self.export_to(Path::new(&path)) {
+                        Ok(()) => format!("Saved the plan to {}", path),
+                        Err(e) => e.to_string(),
+                    });
+                }
+                KeyCode::Esc => self.export = None,
+                _ => {}
+            }
+            return Action::Continue;
+        }
+        self.status = None;
+        let lift = Lift::ALL[self.lift];
+        let last_week = self.program.weeks.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Right | KeyCode::Char('l') => self.week = (self.week + 1).min(last_week),
+            KeyCode::Left | KeyCode::Char('h') => self.week = self.week.saturating_sub(1),
+            KeyCode::PageDown => self.week = (self.week + 4).min(last_week),
+            KeyCode::PageUp => self.week = self.week.saturating_sub(4),
+            KeyCode::Down | KeyCode::Char('j') => self.lift = (self.lift + 1) % Lift::ALL.len(),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.lift = (self.lift + Lift::ALL.len() - 1) % Lift::ALL.len()
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_training_max(lift, 1),
+            KeyCode::Char('-') => self.adjust_training_max(lift, -1),
+            KeyCode::Char(']') => self.adjust_rounding(lift, 1),
+            KeyCode::Char('[') => self.adjust_rounding(lift, -1),
+            KeyCode::Char('e') => self.export = Some(String::new()),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Move the training max for `lift` by one step of its rounding, every cycle moves with it
+    fn adjust_training_max(&mut self, lift: Lift, direction: i64) {
+        let step = self.rounding.get(lift);
+        let next = self.training_max.get(lift).thousandths() + step.thousandths() * direction;
+        self.training_max
+            .set(lift, Weight::from_thousandths(next).max(step));
+        self.rebuild();
+    }
+
+    /// Move the rounding for `lift` by the smallest step the plates can make
+    fn adjust_rounding(&mut self, lift: Lift, direction: i64) {
+        let step = match self.planner.resolution().thousandths() {
+            step if step > 0 => step,
+            _ => 1_000,
+        };
+        let rounding = self.rounding.get(lift).thousandths();
+        let steps = (rounding + step / 2) / step + direction;
+        self.rounding
+            .set(lift, Weight::from_thousandths(steps.max(1) * step));
+        self.rebuild();
+    }
+
+    fn export_to(&self, path: &Path) -> Result<()> {
+        let contents = match path.extension().and_then(|s| s.to_str()) {
+            Some("html") | Some("htm") => {
+                render::html(&self.program, &self.supports, &self.planner, self.options)?
+            }
+            Some("txt") => text::render(&self.program, &self.supports, &self.planner, self.options),
+            _ => Format::of(path)?.to_string(&self.program)?,
+        };
+        file::write(path, contents)
+    }
+
+    /// The lines for the selected week, with the range of lines for the selected day
+    fn body(&self) -> (Vec<String>, (usize, usize)) {
+        let mut lines = Vec::new();
+        let mut selected = (0, 0);
+        let week = match self.program.weeks.get(self.week) {
+            Some(week) => week,
+            None => return (lines, selected),
+        };
+        let options = RenderOptions {
+            changes: true,
+            ..self.options
+        };
+        if !self.supports.conditioning.is_empty() {
+            lines.push(text::conditioning_days(&self.supports, week));
+            lines.push(String::new());
+        }
+        for day in &week.days {
+            let start = lines.len();
+            lines.push(format!(
+                "{}  training max {}, rounded to {}",
+                day.lift.name(),
+                week.training_max.get(day.lift),
+                self.rounding.get(day.lift)
+            ));
+            for jump in self.supports.jumps_for_day(day.lift, week) {
+                lines.push(format!("  > {}", jump.describe()));
+            }
+            let supports = self.supports.for_day(day.lift, week);
+            let rendered = render_day(day, &supports, &self.planner, options);
+            for (set, planned) in rendered.warm_up.iter().zip(&day.warm_up) {
+                lines.push(format!(
+                    "  {}{} x{} warm up {}",
+                    set.value,
+                    set.side,
+                    planned.reps,
+                    set.change.as_deref().unwrap_or_default()
+                ));
+            }
+            for (set, planned) in rendered.work.iter().zip(&day.work) {
+                let target = set
+                    .target
+                    .map(|target| format!(" ({} for a PR)", target))
+                    .unwrap_or_default();
+                lines.push(format!(
+                    "  {}{} x{}{}{} {}",
+                    set.value,
+                    set.side,
+                    planned.reps,
+                    if planned.amrap { "+" } else { "" },
+                    target,
+                    set.change.as_deref().unwrap_or_default()
+                ));
+            }
+            for (support, plates) in supports.iter().zip(&rendered.supports) {
+                let mut line = format!("  + {}", support.describe());
+                for plates in plates.side.iter().chain(&plates.change) {
+                    line.push_str(&format!(" {}", plates));
+                }
+                lines.push(line);
+            }
+            for conditioning in self.supports.conditioning_for_day(day.lift, week) {
+                lines.push(format!("  * {}", conditioning.describe()));
+            }
+            if day.lift == Lift::ALL[self.lift] {
+                selected = (start, lines.len());
+            }
+            lines.push(String::new());
+        }
+        (lines, selected)
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        let header = match self.program.weeks.get(self.week) {
+            Some(week) => format!(
+                "Cycle {} of {}, {}",
+                week.cycle,
+                self.program
+                    .weeks
+                    .last()
+                    .map(|w| w.cycle)
+                    .unwrap_or_default(),
+                week_title(week)
+            ),
+            None => "Nothing to show".to_string(),
+        };
+        let footer = match (&self.export, &self.status) {
+            (Some(path), _) => format!("Export to (.html .txt .toml .json .yaml): {}_", path),
+            (None, Some(status)) => status.clone(),
+            (None, None) => String::new(),
+        };
+        let (body, (start, end)) = self.body();
+        let rows = height.saturating_sub(HEADER_ROWS + FOOTER_ROWS);
+        // keep as much of the selected day on screen as possible, starting with its title
+        let scroll = if end <= rows {
+            0
+        } else {
+            start.min(end - rows)
+        };
+        queue!(out, terminal::Clear(ClearType::All))?;
+        draw_line(out, 0, &header, width, true)?;
+        for (row, line) in body.iter().enumerate().skip(scroll).take(rows) {
+            let selected = row == start;
+            draw_line(out, HEADER_ROWS + row - scroll, line, width, selected)?;
+        }
+        if height >= HEADER_ROWS + FOOTER_ROWS {
+            draw_line(out, height - 2, &footer, width, false)?;
+            draw_line(out, height - 1, HELP, width, false)?;
+        }
+        out.flush()
+    }
+}
+
+const HEADER_ROWS: usize = 2;
+const FOOTER_ROWS: usize = 2;
+const HELP: &str =
+    "<-/-> week  PgUp/PgDn cycle  up/down lift  +/- training max  [/] rounding  e export  q quit";
+
+/// Draw `line` on `row`, cut off at the edge of the terminal
+fn draw_line(
+    out: &mut impl Write,
+    row: usize,
+    line: &str,
+    width: usize,
+    highlight: bool,
+) -> io::Result<()> {
+    let line: String = line.chars().take(width).collect();
+    queue!(out, cursor::MoveTo(0, row as u16))?;
+    if highlight {
+        queue!(
+            out,
+            SetAttribute(Attribute::Reverse),
+            Print(line),
+            SetAttribute(Attribute::Reset)
+        )
+    } else {
+        queue!(out, Print(line))
+    }
+}
+
+/// Puts the terminal back the way it was when the view is closed, even after an error
+struct Screen;
+
+impl Screen {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = Self;
+        crossterm::execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
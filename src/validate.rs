@@ -0,0 +1,474 @@
+use chrono::Weekday;
+use itertools::Itertools;
+
+use crate::{
+    error::{Error, Result},
+    inventory::{Inventory, MAX_PER_SIDE},
+    loading::LoadingPlanner,
+    plan::{Lift, Program, TrainingMax},
+    plates::PlateInventory,
+    supports::{Category, Intensity, Load, Supports},
+    weight::Weight,
+};
+
+/// Every problem found with the inputs for a plan, errors stop the
+/// plan from being generated while warnings are only reported
+#[derive(Debug, Default)]
+pub struct Problems {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Problems {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn error(&mut self, problem: impl Into<String>) {
+        self.errors.push(problem.into());
+    }
+
+    pub fn warn(&mut self, problem: impl Into<String>) {
+        self.warnings.push(problem.into());
+    }
+
+    /// A max provided for `lift` must be a positive number
+    pub fn max(&mut self, lift: Lift, max: f32) {
+        if !is_positive(max) {
+            self.error(format!(
+                "The {} max must be a positive number, found {}",
+                lift.name(),
+                max
+            ));
+        }
+    }
+
+    pub fn bodyweight(&mut self, bodyweight: Weight) {
+        if !bodyweight.is_positive() {
+            self.error(format!(
+                "The bodyweight must be a positive number, found {}",
+                bodyweight
+            ));
+        }
+    }
+
+    /// The training max has to be some of the one rep max, but not more than all of it
+    pub fn training_max_percent(&mut self, lift: Option<Lift>, percent: f32) {
+        if !(is_positive(percent) && percent <= 100.0) {
+            let name = lift
+                .map(|lift| format!("{} ", lift.name()))
+                .unwrap_or_default();
+            self.error(format!(
+                "The {}training max percent must be more than 0 and at most 100, found {}",
+                name, percent
+            ));
+        }
+    }
+
+    /// Every training max must be at least the weight of the bar
+    pub fn training_max(&mut self, training_max: &TrainingMax, bar: Weight) {
+        for &lift in &Lift::ALL {
+            let weight = training_max.get(lift);
+            if weight.is_positive() && weight < bar {
+                self.error(format!(
+                    "The {} training max of {} is lighter than the bar ({})",
+                    lift.name(),
+                    weight,
+                    bar
+                ));
+            }
+        }
+    }
+
+    pub fn months(&mut self, months: u32) {
+        if months == 0 {
+            self.error("At least 1 month must be generated");
+        }
+    }
+
+    pub fn schedule(&mut self, schedule: &[Weekday]) {
+        if schedule.is_empty() {
+            self.error("The schedule must include at least 1 day of the week");
+        }
+    }
+
+    pub fn stall_cycles(&mut self, cycles: u32) {
+        if cycles == 0 {
+            self.error("A lift can only stall over at least 1 cycle");
+        }
+    }
+
+    pub fn days_per_page(&mut self, days_per_page: u8) {
+        if days_per_page == 0 {
+            self.error("At least 1 day must be printed on each page");
+        }
+    }
+
+    pub fn columns(&mut self, columns: Option<usize>) {
+        if columns == Some(0) {
+            self.error("At least 1 week must be printed on each row");
+        }
+    }
+
+    /// The bar and every plate must be a positive weight
+    pub fn inventory(&mut self, inventory: &PlateInventory) {
+        if !inventory.bar().is_positive() {
+            self.error(format!(
+                "The bar must be a positive weight, found {}",
+                inventory.bar()
+            ));
+        }
+        if inventory.collar() < Weight::ZERO {
+            self.error(format!(
+                "Collars can't have a negative weight, found {}",
+                inventory.collar()
+            ));
+        }
+        if inventory.plates().is_empty() {
+            self.warn("No plates were provided, only the empty bar can be used");
+        }
+        for (plate, _) in inventory.plates() {
+            if !plate.is_positive() {
+                self.error(format!("Plates must be a positive weight, found {}", plate));
+            }
+        }
+    }
+
+    pub fn max_imbalance(&mut self, max_imbalance: Weight) {
+        if max_imbalance < Weight::ZERO {
+            self.error(format!(
+                "The maximum imbalance can't be negative, found {}",
+                max_imbalance
+            ));
+        }
+    }
+
+    /// The rounding for `lift` that can actually be used, the requested rounding is
+    /// increased to a multiple of the smallest step the plates can make
+    pub fn rounding(&mut self, lift: Lift, rounding: Weight, planner: &LoadingPlanner) -> Weight {
+        if !rounding.is_positive() {
+            self.error(format!(
+                "The {} rounding must be a positive number, found {}",
+                lift.name(),
+                rounding
+            ));
+            return rounding;
+        }
+        let resolution = planner.resolution().thousandths();
+        let requested = rounding.thousandths();
+        if resolution == 0 || requested % resolution == 0 {
+            return rounding;
+        }
+        let usable =
+            Weight::from_thousandths((requested + resolution - 1) / resolution * resolution);
+        self.warn(format!(
+            "{} weights can't be rounded to {} with the plates provided, {} will be used instead",
+            lift.name(),
+            rounding,
+            usable
+        ));
+        usable
+    }
+
+    pub fn increment(&mut self, lift: Lift, increment: Weight) {
+        if increment < Weight::ZERO {
+            self.error(format!(
+                "The {} increment can't be negative, found {}",
+                lift.name(),
+                increment
+            ));
+        }
+    }
+
+    /// Plates are loaded in pairs so an odd one out can't be used, and only
+    /// so many of a plate can be counted for each side of the bar
+    pub fn owned_plates(&mut self, inventory: &Inventory) {
+        let most = u32::from(MAX_PER_SIDE) * 2;
+        for (plate, count) in inventory.plate_counts() {
+            if count > most {
+                self.error(format!(
+                    "There are {} of the {} plates, no more than {} can be used",
+                    count, plate, most
+                ));
+            }
+        }
+        for plate in inventory.unpaired_plates() {
+            self.warn(format!(
+                "There is an odd number of {} plates, one of them won't be used",
+                plate
+            ));
+        }
+    }
+
+    /// Warn about any set lighter than the bar or that can't be loaded with
+    /// the plates available, if none of the sets can be loaded that is an error.
+    /// Supporting sets on the bar must always be loadable
+    pub fn program(&mut self, program: &Program, supports: &Supports, planner: &LoadingPlanner) {
+        let bar = planner.inventory().empty();
+        let mut too_light: Vec<(Lift, Vec<Weight>)> = Vec::new();
+        let mut unreachable = Vec::new();
+        let mut any_reachable = false;
+        let mut supporting: Vec<(String, Vec<Weight>)> = Vec::new();
+        for week in &program.weeks {
+            for support in Lift::ALL
+                .iter()
+                .flat_map(|lift| supports.for_day(*lift, week))
+            {
+                let weight = match support.bar_weight() {
+                    Some(weight) if weight < bar || !planner.can_load(weight) => weight,
+                    _ => continue,
+                };
+                match supporting
+                    .iter_mut()
+                    .find(|(name, _)| *name == support.name)
+                {
+                    Some((_, weights)) => push_unique(weights, weight),
+                    None => supporting.push((support.name, vec![weight])),
+                }
+            }
+        }
+        for (name, weights) in supporting {
+            self.error(format!(
+                "{:?} sets of {} can't be loaded on the bar ({}) with the plates provided",
+                name,
+                list_weights(weights),
+                bar
+            ));
+        }
+        for day in program.weeks.iter().flat_map(|week| week.days.iter()) {
+            for set in day.sets() {
+                if !set.weight.is_positive() {
+                    // already reported as an invalid max
+                    continue;
+                }
+                if set.weight < bar {
+                    match too_light.iter_mut().find(|(lift, _)| *lift == day.lift) {
+                        Some((_, weights)) => push_unique(weights, set.weight),
+                        None => too_light.push((day.lift, vec![set.weight])),
+                    }
+                } else if planner.can_load(set.weight) {
+                    any_reachable = true;
+                } else {
+                    push_unique(&mut unreachable, set.weight);
+                }
+            }
+        }
+        for (lift, weights) in too_light {
+            self.warn(format!(
+                "{} sets of {} are lighter than the bar ({})",
+                lift.name(),
+                list_weights(weights),
+                bar
+            ));
+        }
+        if unreachable.is_empty() {
+            return;
+        }
+        let weights = list_weights(unreachable);
+        if any_reachable {
+            self.warn(format!(
+                "These weights can't be loaded with the plates provided: {}",
+                weights
+            ));
+        } else {
+            self.error(format!(
+                "None of the planned weights can be loaded with the plates provided: {}",
+                weights
+            ));
+        }
+    }
+
+    /// Every supporting exercise needs at least one set and a load that makes sense
+    pub fn exercises(&mut self, supports: &Supports) {
+        let every_exercise = Lift::ALL
+            .iter()
+            .flat_map(|lift| supports.for_lift(*lift))
+            .chain(&supports.jumps)
+            .chain(&supports.conditioning);
+        for support in every_exercise {
+            if support.sets == Some(0) {
+                self.error(format!("{:?} must have at least 1 set", support.name));
+            }
+            match support.load {
+                Some(Load::Percent { percent, .. }) if !is_positive(percent) => {
+                    self.error(format!(
+                        "The load for {:?} must be a positive percent, found {}",
+                        support.name, percent
+                    ));
+                }
+                Some(Load::Fixed(weight)) if !weight.is_positive() => {
+                    self.error(format!(
+                        "The load for {:?} must be a positive weight, found {}",
+                        support.name, weight
+                    ));
+                }
+                Some(Load::Relative(percent)) if !(percent.is_finite() && percent > -100.0) => {
+                    self.error(format!(
+                        "The load for {:?} can't take off more than all of bodyweight, found {}%",
+                        support.name, percent
+                    ));
+                }
+                Some(Load::Relative(_)) if supports.bodyweight.is_none() => {
+                    self.warn(format!(
+                        "The load for {:?} is relative to bodyweight, but no bodyweight was provided",
+                        support.name
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The supporting exercises need sensible sets and loads, and each day's
+    /// assistance in the `program` should fall within the reps targeted for each category
+    pub fn assistance(&mut self, supports: &Supports, program: &Program) {
+        self.exercises(supports);
+        for &lift in &Lift::ALL {
+            let exercises = supports.for_lift(lift);
+            // only the categories a lift has exercises for are checked
+            for &category in &Category::ALL {
+                if !exercises.iter().any(|s| s.category == Some(category)) {
+                    continue;
+                }
+                let target = supports.target(category);
+                // the weeks with the same volume are reported together
+                let mut short: Vec<(String, Vec<(u32, u32)>)> = Vec::new();
+                for week in &program.weeks {
+                    let volume = supports.volume(lift, week);
+                    // a week without any assistance is taken to be a planned break from it
+                    if volume.is_empty() {
+                        continue;
+                    }
+                    let volume = volume.get(&category).copied().unwrap_or_default();
+                    let compared = if volume.max < target.min {
+                        "less"
+                    } else if volume.min > target.max {
+                        "more"
+                    } else {
+                        continue;
+                    };
+                    let problem = format!(
+                        "{} reps of {} assistance, {} than the {} target",
+                        volume,
+                        category.name(),
+                        compared,
+                        target
+                    );
+                    let week = (week.cycle, week.number);
+                    match short.iter_mut().find(|(p, _)| *p == problem) {
+                        Some((_, weeks)) => weeks.push(week),
+                        None => short.push((problem, vec![week])),
+                    }
+                }
+                let cycles = program.weeks.last().map(|week| week.cycle).unwrap_or(1);
+                for (problem, weeks) in short {
+                    self.warn(format!(
+                        "{} has {} in {}",
+                        lift.name(),
+                        problem,
+                        list_weeks(&weeks, cycles > 1)
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Each week of the `program` should have the number of hard and easy conditioning days asked for
+    pub fn conditioning(&mut self, supports: &Supports, program: &Program) {
+        let days = supports.conditioning_days;
+        let cycles = program.weeks.last().map(|week| week.cycle).unwrap_or(1);
+        for (intensity, target) in [(Intensity::Hard, days.hard), (Intensity::Easy, days.easy)] {
+            let target = match target {
+                Some(target) => target,
+                None => continue,
+            };
+            let mut wrong: Vec<(String, Vec<(u32, u32)>)> = Vec::new();
+            for week in &program.weeks {
+                let (hard, easy) = supports.conditioning_days(week);
+                let count = match intensity {
+                    Intensity::Hard => hard,
+                    Intensity::Easy => easy,
+                };
+                let compared = if (count as u16) < target.min {
+                    "fewer"
+                } else if count as u16 > target.max {
+                    "more"
+                } else {
+                    continue;
+                };
+                let problem = format!(
+                    "{} day{}, {} than the {} asked for",
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    compared,
+                    target
+                );
+                let week = (week.cycle, week.number);
+                match wrong.iter_mut().find(|(p, _)| *p == problem) {
+                    Some((_, weeks)) => weeks.push(week),
+                    None => wrong.push((problem, vec![week])),
+                }
+            }
+            for (problem, weeks) in wrong {
+                self.warn(format!(
+                    "{} conditioning is on {}, in {}",
+                    match intensity {
+                        Intensity::Hard => "Hard",
+                        Intensity::Easy => "Easy",
+                    },
+                    problem,
+                    list_weeks(&weeks, cycles > 1)
+                ));
+            }
+        }
+    }
+
+    /// An `Error::Validation` with every error found, otherwise the warnings
+    pub fn finish(self) -> Result<Vec<String>> {
+        if self.errors.is_empty() {
+            Ok(self.warnings)
+        } else {
+            Err(Error::Validation(self.errors))
+        }
+    }
+}
+
+fn push_unique(weights: &mut Vec<Weight>, weight: Weight) {
+    if !weights.contains(&weight) {
+        weights.push(weight);
+    }
+}
+
+/// Sorted lightest first and comma separated
+fn list_weights(mut weights: Vec<Weight>) -> String {
+    weights.sort();
+    weights
+        .iter()
+        .map(|weight| weight.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// For example `weeks 1, 2` or, when there's more than one cycle, `cycle 1 weeks 1, 2, cycle 2 week 4`
+fn list_weeks(weeks: &[(u32, u32)], name_cycles: bool) -> String {
+    weeks
+        .iter()
+        .group_by(|(cycle, _)| *cycle)
+        .into_iter()
+        .map(|(cycle, weeks)| {
+            let weeks: Vec<String> = weeks.map(|(_, week)| week.to_string()).collect();
+            let plural = if weeks.len() == 1 { "" } else { "s" };
+            let text = format!("week{} {}", plural, weeks.join(", "));
+            if name_cycles {
+                format!("cycle {} {}", cycle, text)
+            } else {
+                text
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn is_positive(weight: f32) -> bool {
+    weight.is_finite() && weight > 0.0
+}
@@ -0,0 +1,330 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Weights are stored as a whole number of these
+const THOUSANDTHS: i64 = 1000;
+
+/// A weight stored exactly as thousandths of a unit, so fractional and micro
+/// plates can be summed, compared and used as map keys without any rounding
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Weight(i64);
+
+impl Weight {
+    pub const ZERO: Self = Self(0);
+
+    pub const fn from_thousandths(thousandths: i64) -> Self {
+        Self(thousandths)
+    }
+
+    pub fn thousandths(self) -> i64 {
+        self.0
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// `fraction` of this weight, to the nearest thousandth
+    pub fn times(self, fraction: f32) -> Self {
+        Self((self.0 as f64 * fraction as f64).round() as i64)
+    }
+
+    /// Round to the nearest whole number (or multiple of `rounding` when it's less than 1)
+    /// and then up to the next multiple of `rounding`, unchanged when `rounding` isn't positive
+    pub fn round_up_to(self, rounding: Self) -> Self {
+        if !rounding.is_positive() {
+            return self;
+        }
+        let unit = rounding.0.min(THOUSANDTHS);
+        let nearest = (self.0 + unit / 2).div_euclid(unit) * unit;
+        Self(-(-nearest).div_euclid(rounding.0) * rounding.0)
+    }
+}
+
+impl From<f32> for Weight {
+    /// Rounded to the nearest thousandth
+    fn from(weight: f32) -> Self {
+        Self((weight as f64 * THOUSANDTHS as f64).round() as i64)
+    }
+}
+
+impl From<Weight> for f32 {
+    fn from(weight: Weight) -> Self {
+        (weight.0 as f64 / THOUSANDTHS as f64) as f32
+    }
+}
+
+impl FromStr for Weight {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed: f64 = s
+            .trim()
+            .parse()
+            .ok()
+            .filter(|weight: &f64| weight.is_finite())
+            .ok_or_else(|| format!("Invalid weight: {:?}", s))?;
+        Ok(Self((parsed * THOUSANDTHS as f64).round() as i64))
+    }
+}
+
+impl fmt::Display for Weight {
+    /// Without any trailing zeros, so 45, 2.5 and 1.25, padded to any width provided
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let whole = (self.0 / THOUSANDTHS).abs();
+        let fraction = (self.0 % THOUSANDTHS).abs();
+        if fraction == 0 {
+            f.pad(&format!("{}{}", sign, whole))
+        } else {
+            let fraction = format!("{:03}", fraction);
+            f.pad(&format!(
+                "{}{}.{}",
+                sign,
+                whole,
+                fraction.trim_end_matches('0')
+            ))
+        }
+    }
+}
+
+impl fmt::Debug for Weight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Add for Weight {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Weight {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Weight {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 - other.0)
+    }
+}
+
+impl Neg for Weight {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Mul<u32> for Weight {
+    type Output = Self;
+    fn mul(self, other: u32) -> Self::Output {
+        Self(self.0 * other as i64)
+    }
+}
+
+impl Sum for Weight {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Weight> for Weight {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Serialize for Weight {
+    /// Whole weights are written as integers and everything else as a decimal
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % THOUSANDTHS == 0 {
+            serializer.serialize_i64(self.0 / THOUSANDTHS)
+        } else {
+            serializer.serialize_f64(self.0 as f64 / THOUSANDTHS as f64)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Weight {
+    /// Any number or a string containing one
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(WeightVisitor)
+    }
+}
+
+struct WeightVisitor;
+
+impl<'de> Visitor<'de> for WeightVisitor {
+    type Value = Weight;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a weight")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        v.checked_mul(THOUSANDTHS)
+            .map(Weight)
+            .ok_or_else(|| E::custom(format!("Weight is too large: {}", v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        let v = i64::try_from(v).map_err(|_| E::custom(format!("Weight is too large: {}", v)))?;
+        self.visit_i64(v)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        if v.is_finite() {
+            Ok(Weight((v * THOUSANDTHS as f64).round() as i64))
+        } else {
+            Err(E::custom(format!("Invalid weight: {}", v)))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Loaded {
+        weight: Weight,
+    }
+
+    /// Every weight from -2 to 2 by thousandths, every microplate sized step up
+    /// to 1000 and a spread of pseudo random weights up to a million
+    fn weights() -> Vec<Weight> {
+        let mut weights: Vec<Weight> = (-2_000..=2_000).map(Weight::from_thousandths).collect();
+        weights.extend((0..=4_000).map(|quarter| Weight::from_thousandths(quarter * 250)));
+        let mut seed: u64 = 0x5eed;
+        for _ in 0..2_000 {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            weights.push(Weight::from_thousandths(
+                (seed >> 33) as i64 % 1_000_000_000,
+            ));
+        }
+        weights
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        for weight in weights() {
+            let loaded = Loaded { weight };
+            let text = toml::to_string(&loaded).unwrap();
+            assert_eq!(toml::from_str::<Loaded>(&text).unwrap(), loaded, "{}", text);
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        for weight in weights() {
+            let loaded = Loaded { weight };
+            let text = serde_json::to_string(&loaded).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Loaded>(&text).unwrap(),
+                loaded,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_through_yaml() {
+        for weight in weights() {
+            let loaded = Loaded { weight };
+            let text = serde_yaml::to_string(&loaded).unwrap();
+            assert_eq!(
+                serde_yaml::from_str::<Loaded>(&text).unwrap(),
+                loaded,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        for weight in weights() {
+            assert_eq!(weight.to_string().parse::<Weight>(), Ok(weight));
+        }
+    }
+
+    #[test]
+    fn whole_weights_are_written_as_integers() {
+        let text = toml::to_string(&Loaded {
+            weight: Weight::from(45.0),
+        })
+        .unwrap();
+        assert_eq!(text.trim(), "weight = 45");
+    }
+
+    #[test]
+    fn fractional_plates_are_distinct() {
+        let plates: std::collections::HashSet<Weight> = ["1.2", "1.25", "0.25", "0.5", "-1", "-2"]
+            .iter()
+            .map(|plate| plate.parse().unwrap())
+            .collect();
+        assert_eq!(plates.len(), 6);
+        assert!(Weight::from(1.2) < Weight::from(1.25));
+        assert!(Weight::from(-2.0) < Weight::from(-1.0));
+    }
+
+    #[test]
+    fn reads_integers_decimals_and_strings() {
+        for text in ["weight = 2", "weight = 2.0", "weight = \"2\""] {
+            assert_eq!(
+                toml::from_str::<Loaded>(text).unwrap().weight,
+                Weight::from_thousandths(2_000)
+            );
+        }
+        assert!(serde_json::from_str::<Loaded>(r#"{"weight": "nan"}"#).is_err());
+    }
+
+    #[test]
+    fn displays_without_trailing_zeros() {
+        assert_eq!(Weight::from(2.5).to_string(), "2.5");
+        assert_eq!(Weight::from(1.25).to_string(), "1.25");
+        assert_eq!(Weight::from(-0.5).to_string(), "-0.5");
+        assert_eq!(format!("{: >4}", Weight::from(45.0)), "  45");
+    }
+
+    #[test]
+    fn rounds_up_to_a_multiple() {
+        let round = |weight: f32, rounding: f32| Weight::from(weight).round_up_to(rounding.into());
+        assert_eq!(round(131.0, 5.0), Weight::from(135.0));
+        assert_eq!(round(135.0, 5.0), Weight::from(135.0));
+        assert_eq!(round(130.4, 5.0), Weight::from(130.0));
+        assert_eq!(round(130.6, 2.5), Weight::from(132.5));
+        assert_eq!(round(101.3, 1.25), Weight::from(101.25));
+        assert_eq!(round(101.3, 0.5), Weight::from(101.5));
+        assert_eq!(round(101.3, 0.0), Weight::from(101.3));
+    }
+
+    #[test]
+    fn takes_a_fraction_to_the_nearest_thousandth() {
+        assert_eq!(Weight::from(200.0).times(0.65), Weight::from(130.0));
+        assert_eq!(Weight::from(185.0).times(0.333), Weight::from(61.605));
+    }
+}